@@ -0,0 +1,15 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use parside::message::GroupItem;
+use parside::CesrGroup;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok((_, group)) = CesrGroup::from_stream_bytes(data) {
+        // whatever was parsed has to encode back without panicking
+        let _ = group.qb64();
+        let _ = group.qb64b();
+        let _ = group.qb2();
+        let _ = group.full_size();
+    }
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use parside::Message;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = Message::from_stream_bytes(data);
+});
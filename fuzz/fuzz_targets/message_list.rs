@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use parside::MessageList;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = MessageList::from_stream_bytes(data);
+});
@@ -0,0 +1,14 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use parside::StreamParser;
+
+fuzz_target!(|data: &[u8]| {
+    // feed in uneven chunks to exercise resumption on incomplete input
+    let mut parser = StreamParser::new();
+    for chunk in data.chunks(7) {
+        parser.feed(chunk);
+        while let Some(Ok(_)) = parser.next() {}
+    }
+    let _ = parser.finish();
+});
@@ -1,4 +1,5 @@
-use nom::error::ErrorKind;
+use nom::error::{ErrorKind, ParseError};
+use nom::Needed;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -12,19 +13,170 @@ pub enum ParsideError {
     #[error("Empty bytes stream passed for parsing")]
     EmptyBytesStream,
 
+    #[error("Need more bytes (at least {0})")]
+    Incomplete(usize),
+
+    #[error("Nested groups fill {actual} of {expected} quadlets declared by the enclosing group")]
+    QuadletCountMismatch { expected: usize, actual: usize },
+
+    /// `actual` is a lower bound when the message runs past the end of the parsed bytes
+    #[error("Version string declares {declared} bytes but message spans {actual}")]
+    VersionSizeMismatch { declared: usize, actual: usize },
+
+    #[error("Message does not fit in {0} buffered bytes")]
+    BufferLimit(usize),
+
+    #[error("Out of order event: {0}")]
+    OutOfOrderEvent(String),
+
+    #[error("Duplicitous event: {0}")]
+    DuplicitousEvent(String),
+
+    #[error("Invalid event: {0}")]
+    InvalidEvent(String),
+
+    /// Event otherwise valid lacking witness receipts, it may be processed again once more arrive
+    #[error("Partially witnessed event: {0}")]
+    PartiallyWitnessed(String),
+
     #[error("Requested variant does not exists")]
     NotExist,
 
-    #[error("Unexpected variant")]
+    #[error("{0}")]
     Unexpected(String),
 
-    #[error("Common error")]
+    #[error("{0}")]
     Common(String),
+
+    #[error("invalid {primitive} ({reason})")]
+    InvalidPrimitive { primitive: String, reason: String },
+
+    /// Error raised while parsing the item at `path` of a CESR group, e.g.
+    /// `TransIdxSigGroups[2].isigers[0]`
+    #[error("{path}: {source} at offset {offset}")]
+    Parse {
+        path: String,
+        /// Counter code of the innermost group being parsed
+        code: Option<String>,
+        /// Index of the failing item inside the innermost group
+        index: Option<usize>,
+        /// Byte offset of the failing item from the beginning of the parsed bytes
+        offset: usize,
+        source: Box<ParsideError>,
+    },
 }
 
-impl<E> From<nom::Err<E>> for ParsideError {
-    fn from(_: nom::Err<E>) -> ParsideError {
-        ParsideError::StreamDeserializationError(ErrorKind::IsNot)
+/// Segment of the path to the item whose parsing failed
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum PathSegment<'a> {
+    Name(&'a str),
+    Index(usize),
+}
+
+impl ParsideError {
+    /// Map error raised while extracting primitive from stream bytes, telling apart
+    /// truncated input from malformed material
+    pub(crate) fn from_extraction(err: anyhow::Error) -> ParsideError {
+        match err.downcast_ref::<cesride::Error>() {
+            Some(cesride::Error::Shortage(_)) | Some(cesride::Error::EmptyMaterial(_)) => {
+                ParsideError::Incomplete(1)
+            }
+            Some(cesride::Error::TooSmall(needed)) => ParsideError::Incomplete(*needed),
+            _ => ParsideError::from(err),
+        }
+    }
+
+    /// Error wrapped by the parse context, the error itself if there is no context
+    pub fn root_cause(&self) -> &ParsideError {
+        match self {
+            ParsideError::Parse { source, .. } => source.root_cause(),
+            err => err,
+        }
+    }
+
+    /// Byte offset of the failing item, `None` if the error was not raised parsing a group
+    pub fn offset(&self) -> Option<usize> {
+        match self {
+            ParsideError::Parse { offset, .. } => Some(*offset),
+            _ => None,
+        }
+    }
+
+    /// Prepend `segment` to the path of error raised while parsing `bytes`.
+    /// Until the error is located, its offset counts the bytes remaining to the end of the stream.
+    pub(crate) fn within(self, segment: PathSegment, bytes: &[u8]) -> ParsideError {
+        let (path, code, index, offset, source) = match self {
+            ParsideError::Incomplete(_) => return self,
+            ParsideError::Parse { path, code, index, offset, source } => {
+                (path, code, index, offset, source)
+            }
+            err => (String::new(), None, None, bytes.len(), Box::new(err)),
+        };
+
+        let separator = if path.is_empty() || path.starts_with('[') { "" } else { "." };
+        let (path, index) = match segment {
+            PathSegment::Name(name) => (format!("{name}{separator}{path}"), index),
+            PathSegment::Index(at) => (format!("[{at}]{separator}{path}"), index.or(Some(at))),
+        };
+        ParsideError::Parse { path, code, index, offset, source }
+    }
+
+    /// Record counter code of the group being parsed, unless an inner group was recorded already
+    pub(crate) fn with_code(self, counter_code: &str) -> ParsideError {
+        match self {
+            ParsideError::Parse { path, code, index, offset, source } => ParsideError::Parse {
+                path,
+                code: code.or_else(|| Some(counter_code.to_string())),
+                index,
+                offset,
+                source,
+            },
+            err => err,
+        }
+    }
+
+    /// Move the error `by` bytes further in the stream
+    pub(crate) fn shifted(self, by: usize) -> ParsideError {
+        match self {
+            ParsideError::Parse { path, code, index, offset, source } => {
+                ParsideError::Parse { path, code, index, offset: offset + by, source }
+            }
+            err => err,
+        }
+    }
+
+    /// Turn the count of remaining bytes into offset from the beginning of `size` bytes long stream
+    pub(crate) fn located(self, size: usize) -> ParsideError {
+        match self {
+            ParsideError::Parse { path, code, index, offset, source } => ParsideError::Parse {
+                path,
+                code,
+                index,
+                offset: size.saturating_sub(offset),
+                source,
+            },
+            err => err,
+        }
+    }
+}
+
+impl<I> ParseError<I> for ParsideError {
+    fn from_error_kind(_input: I, kind: ErrorKind) -> ParsideError {
+        ParsideError::StreamDeserializationError(kind)
+    }
+
+    fn append(_input: I, _kind: ErrorKind, other: ParsideError) -> ParsideError {
+        other
+    }
+}
+
+impl From<nom::Err<ParsideError>> for ParsideError {
+    fn from(err: nom::Err<ParsideError>) -> ParsideError {
+        match err {
+            nom::Err::Incomplete(Needed::Size(needed)) => ParsideError::Incomplete(needed.get()),
+            nom::Err::Incomplete(Needed::Unknown) => ParsideError::Incomplete(1),
+            nom::Err::Error(err) | nom::Err::Failure(err) => err,
+        }
     }
 }
 
@@ -34,4 +186,10 @@ impl From<anyhow::Error> for ParsideError {
     }
 }
 
+impl From<std::io::Error> for ParsideError {
+    fn from(err: std::io::Error) -> ParsideError {
+        ParsideError::Common(err.to_string())
+    }
+}
+
 pub type ParsideResult<T> = Result<T, ParsideError>;
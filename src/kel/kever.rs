@@ -0,0 +1,724 @@
+use cesride::common::Ilkage;
+use cesride::data::{Object, Value};
+use cesride::{matter, Diger, Matter, Prefixer, Sadder, Serder, Siger, Tholder, Verfer};
+
+use crate::error::{ParsideError, ParsideResult};
+use crate::message::{CesrGroup, Group};
+use crate::verify::controller_sigs::verify_sigers;
+use crate::verify::key_state::KeyState;
+use crate::verify::receipts::toad;
+
+/// Material attached to a key event that bears on its validation
+#[derive(Debug, Default)]
+pub(crate) struct Attachments<'a> {
+    pub(crate) sigers: Vec<&'a Siger>,
+    pub(crate) wigers: Vec<&'a Siger>,
+    /// Sequence number and digest of the delegator event anchoring a delegated event
+    pub(crate) source: Option<(u128, String)>,
+}
+
+impl<'a> Attachments<'a> {
+    /// Collect attachments from `groups`, unwrapping pipelined `-V` groups
+    pub(crate) fn collect(groups: &[&'a CesrGroup]) -> ParsideResult<Self> {
+        let mut attachments = Self::default();
+        for group in groups {
+            attachments.add(group)?;
+        }
+        Ok(attachments)
+    }
+
+    fn add(&mut self, group: &'a CesrGroup) -> ParsideResult<()> {
+        match group {
+            CesrGroup::ControllerIdxSigsVariant { value } => {
+                self.sigers.extend(value.value().iter().map(|sig| &sig.siger))
+            }
+            CesrGroup::WitnessIdxSigsVariant { value } => {
+                self.wigers.extend(value.value().iter().map(|sig| &sig.siger))
+            }
+            CesrGroup::SealSourceCouplesVariant { value } => {
+                // delegated events are anchored by a single delegating event
+                if self.source.is_some() || value.value().len() > 1 {
+                    return Err(invalid("Key event with more than one seal source".to_string()));
+                }
+                if let Some(couple) = value.value().first() {
+                    self.source = Some((couple.seqner.sn()?, couple.saider.qb64()?));
+                }
+            }
+            CesrGroup::AttachedMaterialQuadletsVariant { value } => {
+                for group in value.value() {
+                    self.add(group)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// Key event validated against the key state, applied once its delegation is approved
+#[derive(Debug)]
+pub(crate) struct Accepted {
+    pub(crate) said: String,
+    pub(crate) sn: u128,
+    /// Delegator approving the event, `None` for events of prefixes not delegated
+    pub(crate) delegator: Option<String>,
+    seals: Vec<Value>,
+    establishment: Option<Establishment>,
+}
+
+/// Key state established by a validated establishment event
+#[derive(Debug)]
+struct Establishment {
+    state: KeyState,
+    digers: Vec<Diger>,
+    ntholder: Option<Tholder>,
+    witnesses: Vec<String>,
+    toad: u32,
+}
+
+/// Key state of a transferable prefix, built from its key event log (KEL) one event at a time
+/// by [`crate::Kevery`]
+#[derive(Debug, Clone)]
+pub struct Kever {
+    prefix: String,
+    /// Digests of the accepted events, indexed by sequence number
+    saids: Vec<String>,
+    /// Seals anchored by the accepted events, indexed by sequence number
+    seals: Vec<Vec<Value>>,
+    /// Key states established by the accepted establishment events, the one in force last
+    establishments: Vec<KeyState>,
+    digers: Vec<Diger>,
+    ntholder: Option<Tholder>,
+    witnesses: Vec<String>,
+    toad: u32,
+    delegator: Option<String>,
+}
+
+impl Kever {
+    /// Identifier prefix of the KEL
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Sequence number of the last accepted event
+    pub fn sn(&self) -> u128 {
+        self.saids.len() as u128 - 1
+    }
+
+    /// Digest of the last accepted event
+    pub fn said(&self) -> &str {
+        &self.saids[self.saids.len() - 1]
+    }
+
+    /// Signing keys and threshold in force
+    pub fn key_state(&self) -> &KeyState {
+        &self.establishments[self.establishments.len() - 1]
+    }
+
+    /// Key state established by the accepted event at `sn`, `None` unless its digest is `said`
+    pub fn establishment(&self, sn: u128, said: &str) -> Option<&KeyState> {
+        self.establishments.iter().find(|state| state.sn == sn && state.said == said)
+    }
+
+    /// Digests of the next keys the prefix pre-rotated to
+    pub fn digers(&self) -> &[Diger] {
+        &self.digers
+    }
+
+    /// Threshold of the next keys
+    pub fn ntholder(&self) -> Option<&Tholder> {
+        self.ntholder.as_ref()
+    }
+
+    /// Prefixes of the witnesses in force
+    pub fn witnesses(&self) -> &[String] {
+        &self.witnesses
+    }
+
+    /// Threshold of accountable duplicity, number of witness receipts events need
+    pub fn toad(&self) -> u32 {
+        self.toad
+    }
+
+    /// Prefix of the delegator, `None` unless the prefix is delegated
+    pub fn delegator(&self) -> Option<&str> {
+        self.delegator.as_deref()
+    }
+
+    /// Seals anchored by the accepted event at `sn`
+    pub(crate) fn seals(&self, sn: u128) -> Option<&[Value]> {
+        self.seals.get(usize::try_from(sn).ok()?).map(Vec::as_slice)
+    }
+
+    /// Digest of the accepted event at `sn`
+    pub(crate) fn said_at(&self, sn: u128) -> Option<&str> {
+        self.saids.get(usize::try_from(sn).ok()?).map(String::as_str)
+    }
+
+    /// Validate inception event `serder` of a prefix not seen before
+    pub(crate) fn validate_inception(
+        serder: &Serder,
+        attachments: &Attachments,
+    ) -> ParsideResult<Accepted> {
+        let ked = serder.ked().to_map()?;
+        let ilk = string(&ked, "t")?;
+        let said = serder.said()?;
+        let sn = serder.sn()?;
+        if ilk != Ilkage::icp && ilk != Ilkage::dip {
+            return Err(ParsideError::OutOfOrderEvent(format!(
+                "{ilk} event {said} of prefix {} not incepted yet",
+                serder.pre()?
+            )));
+        }
+        if sn != 0 {
+            return Err(invalid(format!("Inception event {said} at sequence number {sn}")));
+        }
+
+        let prefixer = Prefixer::new_with_qb64(&serder.pre()?)?;
+        if !prefixer.verify(&serder.ked(), Some(true))? {
+            return Err(invalid(format!(
+                "Prefix of inception event {said} is not derived from it"
+            )));
+        }
+        if is_digest(&prefixer.code()) {
+            // self-addressing prefixes are the digest of the event with both `i` and `d` dummied
+            if said != prefixer.qb64()? {
+                return Err(invalid(format!("Digest {said} of inception event is not its prefix")));
+            }
+        } else {
+            check_said(serder)?;
+        }
+
+        let delegator = if ilk == Ilkage::dip { Some(string(&ked, "di")?) } else { None };
+        let establishment = Self::establish(serder, &ked, attachments, None)?;
+        Ok(Accepted {
+            said,
+            sn,
+            delegator,
+            seals: seals(&ked)?,
+            establishment: Some(establishment),
+        })
+    }
+
+    /// Key state of a prefix incepted by `accepted`
+    pub(crate) fn new(accepted: Accepted) -> ParsideResult<Self> {
+        let establishment = accepted.establishment.ok_or_else(|| {
+            ParsideError::Unexpected("Inception without established key state".to_string())
+        })?;
+        Ok(Self {
+            prefix: establishment.state.prefix.clone(),
+            saids: vec![accepted.said],
+            seals: vec![accepted.seals],
+            establishments: vec![establishment.state],
+            digers: establishment.digers,
+            ntholder: establishment.ntholder,
+            witnesses: establishment.witnesses,
+            toad: establishment.toad,
+            delegator: accepted.delegator,
+        })
+    }
+
+    /// Validate event `serder` following the accepted events, `None` if the event was accepted
+    /// already
+    pub(crate) fn validate(
+        &self,
+        serder: &Serder,
+        attachments: &Attachments,
+    ) -> ParsideResult<Option<Accepted>> {
+        let ked = serder.ked().to_map()?;
+        let ilk = string(&ked, "t")?;
+        let said = serder.said()?;
+        let sn = serder.sn()?;
+
+        if sn <= self.sn() {
+            let accepted = self.said_at(sn).unwrap_or_default();
+            if accepted == said {
+                return Ok(None);
+            }
+            // only events the controller signed are evidence of duplicity
+            check_said(serder)?;
+            self.check_conflicting_sigs(serder, &ilk, sn, attachments)?;
+            return Err(ParsideError::DuplicitousEvent(format!(
+                "{ilk} event {said} at sequence number {sn} of {} conflicts with accepted event \
+                 {accepted}",
+                self.prefix
+            )));
+        }
+        if sn > self.sn() + 1 {
+            return Err(ParsideError::OutOfOrderEvent(format!(
+                "{ilk} event {said} at sequence number {sn} of {} follows sequence number {}",
+                self.prefix,
+                self.sn()
+            )));
+        }
+
+        if self.digers.is_empty() {
+            return Err(invalid(format!(
+                "Prefix {} abandoned its KEL, {ilk} event {said} is not allowed",
+                self.prefix
+            )));
+        }
+
+        check_said(serder)?;
+        let prior = string(&ked, "p")?;
+        if prior != self.said() {
+            return Err(invalid(format!(
+                "Prior digest {prior} of event {said} is not the digest of event {}",
+                self.said()
+            )));
+        }
+
+        let establishment = match ilk.as_str() {
+            Ilkage::rot | Ilkage::drt => {
+                if (ilk == Ilkage::drt) != self.delegator.is_some() {
+                    return Err(invalid(format!(
+                        "{ilk} event {said} does not match delegation of prefix {}",
+                        self.prefix
+                    )));
+                }
+                Some(Self::establish(serder, &ked, attachments, Some(self))?)
+            }
+            Ilkage::ixn => {
+                let state = self.key_state();
+                check_sigs(serder, attachments, &state.verfers, &state.tholder)?;
+                check_wigs(serder, attachments, &self.witnesses, self.toad)?;
+                None
+            }
+            _ => return Err(invalid(format!("Unexpected {ilk} event {said} following inception"))),
+        };
+
+        let delegator = if ilk == Ilkage::drt { self.delegator.clone() } else { None };
+        Ok(Some(Accepted { said, sn, delegator, seals: seals(&ked)?, establishment }))
+    }
+
+    /// Check the controller signatures of event `serder` conflicting with the accepted event at
+    /// `sn`, against the keys establishment events declare or else the keys in force before `sn`
+    fn check_conflicting_sigs(
+        &self,
+        serder: &Serder,
+        ilk: &str,
+        sn: u128,
+        attachments: &Attachments,
+    ) -> ParsideResult<()> {
+        let said = serder.said()?;
+        match ilk {
+            Ilkage::icp | Ilkage::dip | Ilkage::rot | Ilkage::drt => {
+                let tholder = serder.tholder()?.ok_or_else(|| {
+                    invalid(format!("Establishment event {said} without threshold"))
+                })?;
+                check_sigs(serder, attachments, &serder.verfers()?, &tholder)?;
+            }
+            _ => {
+                let state =
+                    self.establishments.iter().rev().find(|state| state.sn < sn).ok_or_else(
+                        || invalid(format!("{ilk} event {said} replacing inception")),
+                    )?;
+                check_sigs(serder, attachments, &state.verfers, &state.tholder)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply event validated by [`Kever::validate`]
+    pub(crate) fn apply(&mut self, accepted: Accepted) {
+        self.saids.push(accepted.said);
+        self.seals.push(accepted.seals);
+        if let Some(establishment) = accepted.establishment {
+            self.establishments.push(establishment.state);
+            self.digers = establishment.digers;
+            self.ntholder = establishment.ntholder;
+            self.witnesses = establishment.witnesses;
+            self.toad = establishment.toad;
+        }
+    }
+
+    /// Validate the keys, thresholds and witnesses established by inception or rotation event
+    /// `serder`. Rotations have to expose keys satisfying the next threshold of the `prior` state.
+    fn establish(
+        serder: &Serder,
+        ked: &Object,
+        attachments: &Attachments,
+        prior: Option<&Kever>,
+    ) -> ParsideResult<Establishment> {
+        let said = serder.said()?;
+        let verfers = serder.verfers()?;
+        let tholder = serder
+            .tholder()?
+            .ok_or_else(|| invalid(format!("Establishment event {said} without threshold")))?;
+        check_threshold(&tholder, verfers.len(), "signing", &said)?;
+        let digers = serder.digers()?;
+        let ntholder = serder.ntholder()?;
+        match &ntholder {
+            Some(ntholder) => check_threshold(ntholder, digers.len(), "next", &said)?,
+            None if !digers.is_empty() => {
+                return Err(invalid(format!("Establishment event {said} without next threshold")))
+            }
+            None => {}
+        }
+
+        let verified = check_sigs(serder, attachments, &verfers, &tholder)?;
+        if let Some(prior) = prior {
+            let prior_ntholder = match &prior.ntholder {
+                Some(ntholder) if !prior.digers.is_empty() => ntholder,
+                _ => {
+                    return Err(invalid(format!(
+                        "Prefix {} committed to no next keys, rotation {said} is not allowed",
+                        prior.prefix
+                    )))
+                }
+            };
+            // prior next key digests exposed by the keys signing the rotation
+            let mut exposed = vec![];
+            for index in &verified {
+                let key = verfers[*index as usize].qb64b()?;
+                for (at, diger) in prior.digers.iter().enumerate() {
+                    if diger.verify(&key)? && !exposed.contains(&(at as u32)) {
+                        exposed.push(at as u32);
+                    }
+                }
+            }
+            if !prior_ntholder.satisfy(&exposed)? {
+                return Err(invalid(format!(
+                    "Keys signing rotation {said} do not satisfy the prior next threshold"
+                )));
+            }
+        }
+
+        let witnesses = match prior {
+            None => strings(ked, "b")?,
+            Some(prior) => Self::rotate_witnesses(ked, &prior.witnesses, &said)?,
+        };
+        if witnesses.iter().enumerate().any(|(at, w)| witnesses[..at].contains(w)) {
+            return Err(invalid(format!("Duplicate witnesses in event {said}")));
+        }
+        let toad = toad(serder).map_err(|err| invalid(err.to_string()))?;
+        let fits = match witnesses.len() {
+            0 => toad == 0,
+            count => (1..=count).contains(&(toad as usize)),
+        };
+        if !fits {
+            return Err(invalid(format!(
+                "Witness threshold {toad} of event {said} does not fit {} witnesses",
+                witnesses.len()
+            )));
+        }
+        check_wigs(serder, attachments, &witnesses, toad)?;
+
+        Ok(Establishment {
+            state: KeyState { prefix: serder.pre()?, sn: serder.sn()?, said, verfers, tholder },
+            digers,
+            ntholder,
+            witnesses,
+            toad,
+        })
+    }
+
+    /// Witnesses following rotation event `ked` cutting `br` from and adding `ba` to `witnesses`
+    fn rotate_witnesses(
+        ked: &Object,
+        witnesses: &[String],
+        said: &str,
+    ) -> ParsideResult<Vec<String>> {
+        let cuts = strings(ked, "br")?;
+        let adds = strings(ked, "ba")?;
+        for (at, cut) in cuts.iter().enumerate() {
+            if !witnesses.contains(cut) || cuts[..at].contains(cut) {
+                return Err(invalid(format!("Event {said} cuts unknown witness {cut}")));
+            }
+        }
+        let mut rotated =
+            witnesses.iter().filter(|witness| !cuts.contains(witness)).cloned().collect::<Vec<_>>();
+        for add in adds {
+            if rotated.contains(&add) || cuts.contains(&add) {
+                return Err(invalid(format!("Event {said} adds witness {add} already in force")));
+            }
+            rotated.push(add);
+        }
+        Ok(rotated)
+    }
+}
+
+fn invalid(reason: String) -> ParsideError {
+    ParsideError::InvalidEvent(reason)
+}
+
+fn is_digest(code: &str) -> bool {
+    [
+        matter::Codex::Blake3_256,
+        matter::Codex::Blake3_512,
+        matter::Codex::Blake2b_256,
+        matter::Codex::Blake2b_512,
+        matter::Codex::Blake2s_256,
+        matter::Codex::SHA3_256,
+        matter::Codex::SHA3_512,
+        matter::Codex::SHA2_256,
+        matter::Codex::SHA2_512,
+    ]
+    .contains(&code)
+}
+
+/// Check the `d` field of the event is the digest of the event
+fn check_said(serder: &Serder) -> ParsideResult<()> {
+    if !serder.saider().verify(&serder.ked(), Some(true), Some(true), None, None, None)? {
+        return Err(invalid(format!("Digest {} does not match event", serder.said()?)));
+    }
+    Ok(())
+}
+
+/// Check threshold `tholder` fits `count` keys
+fn check_threshold(tholder: &Tholder, count: usize, name: &str, said: &str) -> ParsideResult<()> {
+    let size = tholder.size() as usize;
+    if size > count || (tholder.weighted() && size != count) {
+        return Err(invalid(format!(
+            "The {name} threshold of event {said} does not fit {count} keys"
+        )));
+    }
+    Ok(())
+}
+
+/// Check the controller signatures of the event satisfy `tholder` of `verfers`, returning the
+/// verified indices
+fn check_sigs(
+    serder: &Serder,
+    attachments: &Attachments,
+    verfers: &[Verfer],
+    tholder: &Tholder,
+) -> ParsideResult<Vec<u32>> {
+    let (verified, _) = verify_sigers(&serder.raw(), attachments.sigers.clone(), verfers)?;
+    if !tholder.satisfy(&verified)? {
+        return Err(invalid(format!(
+            "Signatures of event {} do not satisfy the signing threshold",
+            serder.said()?
+        )));
+    }
+    Ok(verified)
+}
+
+/// Check the event is receipted by `toad` of its `witnesses`
+fn check_wigs(
+    serder: &Serder,
+    attachments: &Attachments,
+    witnesses: &[String],
+    toad: u32,
+) -> ParsideResult<()> {
+    if toad == 0 {
+        return Ok(());
+    }
+    let verfers = witnesses
+        .iter()
+        .map(|witness| Verfer::new_with_qb64(witness))
+        .collect::<Result<Vec<_>, _>>()?;
+    let (verified, _) = verify_sigers(&serder.raw(), attachments.wigers.clone(), &verfers)?;
+    if verified.len() < toad as usize {
+        return Err(ParsideError::PartiallyWitnessed(format!(
+            "Event {} has {} of {toad} witness receipts",
+            serder.said()?,
+            verified.len()
+        )));
+    }
+    Ok(())
+}
+
+fn field<'a>(ked: &'a Object, label: &str) -> ParsideResult<&'a Value> {
+    ked.get(label).ok_or_else(|| invalid(format!("Event without {label:?} field")))
+}
+
+fn string(ked: &Object, label: &str) -> ParsideResult<String> {
+    Ok(field(ked, label)?.to_string()?)
+}
+
+fn strings(ked: &Object, label: &str) -> ParsideResult<Vec<String>> {
+    field(ked, label)?.to_vec()?.iter().map(|value| Ok(value.to_string()?)).collect()
+}
+
+/// Seals anchored by the event in its `a` field
+fn seals(ked: &Object) -> ParsideResult<Vec<Value>> {
+    match ked.get("a") {
+        Some(seals) => Ok(seals.to_vec()?),
+        None => Ok(vec![]),
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::utils::fixtures::{digests, incept, keys, saidify, signer, signers};
+    use cesride::{indexer, Signer};
+    use serde_json::json;
+
+    fn sign(serder: &Serder, signers: &[(u32, &Signer)]) -> Vec<Siger> {
+        signers
+            .iter()
+            .map(|(i, signer)| signer.sign_indexed(&serder.raw(), false, *i, None).unwrap())
+            .collect()
+    }
+
+    fn attachments<'a>(sigers: &'a [Siger], wigers: &'a [Siger]) -> Attachments<'a> {
+        Attachments {
+            sigers: sigers.iter().collect(),
+            wigers: wigers.iter().collect(),
+            source: None,
+        }
+    }
+
+    fn interact(prior: &Serder, sn: u128) -> Serder {
+        saidify(
+            json!({
+                "v": "", "t": "ixn", "d": "", "i": prior.pre().unwrap(), "s": format!("{sn:x}"),
+                "p": prior.said().unwrap(), "a": []
+            }),
+            &["d"],
+        )
+    }
+
+    #[test]
+    pub fn test_validate_inception() {
+        let (key_0, key_1) = (signer(0, true), signer(1, true));
+        let icp = incept(&[&key_0], &[&key_1], &[], "0");
+        let sigers = sign(&icp, &[(0, &key_0)]);
+        let accepted = Kever::validate_inception(&icp, &attachments(&sigers, &[])).unwrap();
+        assert_eq!(0, accepted.sn);
+        assert!(accepted.delegator.is_none());
+
+        let kever = Kever::new(accepted).unwrap();
+        assert_eq!(icp.pre().unwrap(), kever.prefix());
+        assert_eq!(vec![key_0.verfer()], kever.key_state().verfers);
+
+        // events following inception need the key state of the prefix
+        let ixn = interact(&icp, 1);
+        let sigers = sign(&ixn, &[(0, &key_0)]);
+        let err = Kever::validate_inception(&ixn, &attachments(&sigers, &[])).unwrap_err();
+        assert!(matches!(err, ParsideError::OutOfOrderEvent(..)));
+    }
+
+    #[test]
+    pub fn test_validate_following_events() {
+        let (key_0, key_1) = (signer(0, true), signer(1, true));
+        let icp = incept(&[&key_0], &[&key_1], &[], "0");
+        let sigers = sign(&icp, &[(0, &key_0)]);
+        let accepted = Kever::validate_inception(&icp, &attachments(&sigers, &[])).unwrap();
+        let mut kever = Kever::new(accepted).unwrap();
+
+        let ixn = interact(&icp, 1);
+        let sigers = sign(&ixn, &[(0, &key_0)]);
+        let accepted = kever.validate(&ixn, &attachments(&sigers, &[])).unwrap().unwrap();
+        assert!(accepted.establishment.is_none());
+        kever.apply(accepted);
+        assert_eq!(1, kever.sn());
+        assert_eq!(ixn.said().unwrap(), kever.said());
+
+        // accepted events validate to nothing to apply
+        assert!(kever.validate(&ixn, &attachments(&sigers, &[])).unwrap().is_none());
+
+        let ixn_2 = interact(&ixn, 2);
+        let sigers = sign(&ixn_2, &[(0, &key_1)]);
+        let err = kever.validate(&ixn_2, &attachments(&sigers, &[])).unwrap_err();
+        assert!(matches!(err, ParsideError::InvalidEvent(..)));
+        let ixn_3 = interact(&ixn_2, 3);
+        let sigers = sign(&ixn_3, &[(0, &key_0)]);
+        let err = kever.validate(&ixn_3, &attachments(&sigers, &[])).unwrap_err();
+        assert!(matches!(err, ParsideError::OutOfOrderEvent(..)));
+        assert_eq!(1, kever.sn());
+    }
+
+    #[test]
+    pub fn test_establish_weighted_thresholds() {
+        let keyring = signers(3, true);
+        let (key_0, key_1, key_2) = (&keyring[0], &keyring[1], &keyring[2]);
+        let weighted = |kt: serde_json::Value| {
+            saidify(
+                json!({
+                    "v": "", "t": "icp", "d": "", "i": "", "s": "0",
+                    "kt": kt, "k": keys(&[key_0, key_1, key_2]),
+                    "nt": "1", "n": digests(&[key_0]), "bt": "0", "b": [], "c": [], "a": []
+                }),
+                &["i", "d"],
+            )
+        };
+
+        let icp = weighted(json!(["1/2", "1/2", "1/4"]));
+        let sigers = sign(&icp, &[(0, key_0), (1, key_1)]);
+        assert!(Kever::validate_inception(&icp, &attachments(&sigers, &[])).is_ok());
+        let sigers = sign(&icp, &[(0, key_0), (2, key_2)]);
+        let err = Kever::validate_inception(&icp, &attachments(&sigers, &[])).unwrap_err();
+        assert!(matches!(err, ParsideError::InvalidEvent(..)));
+
+        // weighted thresholds weigh every key
+        let icp = weighted(json!(["1/2", "1/2"]));
+        let sigers = sign(&icp, &[(0, key_0), (1, key_1)]);
+        let err = Kever::validate_inception(&icp, &attachments(&sigers, &[])).unwrap_err();
+        assert!(matches!(err, ParsideError::InvalidEvent(..)));
+    }
+
+    #[test]
+    pub fn test_establish_witness_thresholds() {
+        let (key_0, key_1) = (signer(0, true), signer(1, true));
+        let (witness_0, witness_1) = (signer(8, false), signer(9, false));
+        let invalid = |icp: &Serder, wigers: &[Siger]| {
+            let sigers = sign(icp, &[(0, &key_0)]);
+            matches!(
+                Kever::validate_inception(icp, &attachments(&sigers, wigers)),
+                Err(ParsideError::InvalidEvent(..))
+            )
+        };
+
+        let icp = incept(&[&key_0], &[&key_1], &[&witness_0], "2");
+        assert!(invalid(&icp, &sign(&icp, &[(0, &witness_0)])));
+        let icp = incept(&[&key_0], &[&key_1], &[], "1");
+        assert!(invalid(&icp, &[]));
+        let icp = incept(&[&key_0], &[&key_1], &[&witness_0, &witness_1], "0");
+        assert!(invalid(&icp, &[]));
+        let icp = incept(&[&key_0], &[&key_1], &[&witness_0, &witness_0], "1");
+        assert!(invalid(&icp, &sign(&icp, &[(0, &witness_0)])));
+    }
+
+    #[test]
+    pub fn test_check_sigs_counts_duplicates_once() {
+        let (key_0, key_1) = (signer(0, true), signer(1, true));
+        let icp = incept(&[&key_0, &key_1], &[&key_1], &[], "0");
+        let verfers = icp.verfers().unwrap();
+        let tholder = icp.tholder().unwrap().unwrap();
+
+        let sigers = sign(&icp, &[(0, &key_0), (0, &key_0)]);
+        let err = check_sigs(&icp, &attachments(&sigers, &[]), &verfers, &tholder).unwrap_err();
+        assert!(matches!(err, ParsideError::InvalidEvent(..)));
+
+        let sigers = sign(&icp, &[(0, &key_0), (1, &key_1), (0, &key_0)]);
+        let verified = check_sigs(&icp, &attachments(&sigers, &[]), &verfers, &tholder).unwrap();
+        assert_eq!(vec![0, 1], verified);
+    }
+
+    #[test]
+    pub fn test_check_sigs_ignores_malformed_sigs() {
+        let key_0 =
+            Signer::new_with_raw(&[1; 32], Some(true), Some(matter::Codex::ECDSA_256k1_Seed))
+                .unwrap();
+        let key_1 = signer(1, true);
+        let icp = incept(&[&key_0], &[&key_1], &[], "0");
+
+        // a relayed event stays valid with a junk signature added
+        let junk =
+            Siger::new_with_raw(&[0; 64], None, Some(0), None, Some(indexer::Codex::ECDSA_256k1))
+                .unwrap();
+        let sigers = [vec![junk.clone()], sign(&icp, &[(0, &key_0)])].concat();
+        assert!(Kever::validate_inception(&icp, &attachments(&sigers, &[])).is_ok());
+        let err = Kever::validate_inception(&icp, &attachments(&[junk], &[])).unwrap_err();
+        assert!(matches!(err, ParsideError::InvalidEvent(..)));
+    }
+
+    #[test]
+    pub fn test_check_wigs_counts_duplicates_once() {
+        let key_0 = signer(0, true);
+        let (witness_0, witness_1) = (signer(8, false), signer(9, false));
+        let icp = incept(&[&key_0], &[], &[&witness_0, &witness_1], "2");
+        let witnesses = keys(&[&witness_0, &witness_1]);
+
+        let wigers = sign(&icp, &[(0, &witness_0), (0, &witness_0)]);
+        let err = check_wigs(&icp, &attachments(&[], &wigers), &witnesses, 2).unwrap_err();
+        assert!(matches!(err, ParsideError::PartiallyWitnessed(..)));
+
+        let wigers = sign(&icp, &[(0, &witness_0), (1, &witness_1)]);
+        assert!(check_wigs(&icp, &attachments(&[], &wigers), &witnesses, 2).is_ok());
+        assert!(check_wigs(&icp, &attachments(&[], &[]), &witnesses, 0).is_ok());
+    }
+}
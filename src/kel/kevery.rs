@@ -0,0 +1,460 @@
+use std::collections::HashMap;
+
+use cesride::Serder;
+
+use crate::error::{ParsideError, ParsideResult};
+use crate::kel::kever::{Accepted, Attachments, Kever};
+use crate::message::{CesrGroup, Event, EventBody};
+use crate::verify::key_state::{KeyState, KeyStateProvider};
+
+/// Validator of key event logs, keeping the key state of every prefix it accepted events of.
+/// Events are validated in order against the key state of their prefix: sequence number, prior
+/// digest, pre-rotated next keys, signing and witness thresholds, witness rotations and, for
+/// delegated prefixes, the seal anchoring the event in the KEL of the delegator.
+/// Events arriving ahead of the events they build on fail with
+/// [`ParsideError::OutOfOrderEvent`], signed events conflicting with accepted ones fail with
+/// [`ParsideError::DuplicitousEvent`], events lacking witness receipts fail with
+/// [`ParsideError::PartiallyWitnessed`] and may be processed again once more receipts arrive, and
+/// events breaking the rules fail with [`ParsideError::InvalidEvent`], as do all events following
+/// an establishment event committing to no next keys. Recovery of interaction events by later
+/// rotations is not supported.
+#[derive(Debug, Clone, Default)]
+pub struct Kevery {
+    kevers: HashMap<String, Kever>,
+}
+
+impl Kevery {
+    /// Validator knowing no prefixes yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Key state of `prefix`, `None` until its inception is accepted
+    pub fn kever(&self, prefix: &str) -> Option<&Kever> {
+        self.kevers.get(prefix)
+    }
+
+    /// Validate the message body of `event` together with its attachments. Native bodies are not
+    /// supported yet.
+    pub fn process_event(&mut self, event: &Event) -> ParsideResult<()> {
+        let body = match event.body {
+            EventBody::Custom { value } => value,
+            EventBody::Native { .. } => {
+                return Err(ParsideError::Unexpected(
+                    "Validation of native message bodies is not supported".to_string(),
+                ))
+            }
+        };
+        self.process(&body.serder()?, &event.attachments)
+    }
+
+    /// Validate key event `serder` with the `attachments` following it in the stream, adding it
+    /// to the KEL of its prefix. Events accepted before are accepted again without effect.
+    pub fn process(&mut self, serder: &Serder, attachments: &[&CesrGroup]) -> ParsideResult<()> {
+        let attachments = Attachments::collect(attachments)?;
+        let prefix = serder.pre()?;
+        let accepted = match self.kevers.get(&prefix) {
+            Some(kever) => match kever.validate(serder, &attachments)? {
+                Some(accepted) => accepted,
+                None => return Ok(()),
+            },
+            None => Kever::validate_inception(serder, &attachments)?,
+        };
+        if let Some(delegator) = &accepted.delegator {
+            self.check_delegation(delegator, &prefix, &accepted, &attachments)?;
+        }
+
+        match self.kevers.get_mut(&prefix) {
+            Some(kever) => kever.apply(accepted),
+            None => {
+                self.kevers.insert(prefix, Kever::new(accepted)?);
+            }
+        }
+        Ok(())
+    }
+
+    /// Check delegated event `accepted` is anchored by a seal in the accepted event of the
+    /// `delegator` its seal source couple refers to
+    fn check_delegation(
+        &self,
+        delegator: &str,
+        prefix: &str,
+        accepted: &Accepted,
+        attachments: &Attachments,
+    ) -> ParsideResult<()> {
+        let said = &accepted.said;
+        let (sn, source) = attachments.source.as_ref().ok_or_else(|| {
+            ParsideError::InvalidEvent(format!("Delegated event {said} without seal source"))
+        })?;
+        let kever = self.kevers.get(delegator).ok_or_else(|| {
+            ParsideError::OutOfOrderEvent(format!("Delegator {delegator} of {said} not seen yet"))
+        })?;
+        let seals = match (kever.said_at(*sn), kever.seals(*sn)) {
+            (Some(accepted), Some(seals)) if accepted == source => seals,
+            (Some(_), _) => {
+                return Err(ParsideError::InvalidEvent(format!(
+                    "Seal source {source} of {said} is not an event of delegator {delegator}"
+                )))
+            }
+            _ => {
+                return Err(ParsideError::OutOfOrderEvent(format!(
+                    "Delegating event {source} of {said} not seen yet"
+                )))
+            }
+        };
+
+        let sn = format!("{:x}", accepted.sn);
+        let anchored = seals.iter().any(|seal| {
+            let matches = |label: &str, value: &str| {
+                seal.to_map().ok().and_then(|seal| seal.get(label)?.to_string().ok()).as_deref()
+                    == Some(value)
+            };
+            matches("i", prefix) && matches("s", &sn) && matches("d", said)
+        });
+        if !anchored {
+            return Err(ParsideError::InvalidEvent(format!(
+                "Delegated event {said} is not anchored by delegator {delegator}"
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl KeyStateProvider for Kevery {
+    fn establishment(&self, prefix: &str, sn: u128, said: &str) -> Option<KeyState> {
+        self.kevers.get(prefix)?.establishment(sn, said).cloned()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::message::{
+        ControllerIdxSig, ControllerIdxSigs, Group, GroupItem, SealSourceCouple, SealSourceCouples,
+        WitnessIdxSig, WitnessIdxSigs,
+    };
+    use crate::utils::fixtures::{digests, incept, keys, saidify, signer};
+    use crate::MessageList;
+    use cesride::{Matter, Sadder, Saider, Seqner, Signer};
+    use serde_json::{json, Value as JsonValue};
+
+    fn delegated_incept(signer: &Signer, next: &Signer, delegator: &str) -> Serder {
+        saidify(
+            json!({
+                "v": "", "t": "dip", "d": "", "i": "", "s": "0",
+                "kt": "1", "k": keys(&[signer]), "nt": "1", "n": digests(&[next]),
+                "bt": "0", "b": [], "c": [], "a": [], "di": delegator
+            }),
+            &["i", "d"],
+        )
+    }
+
+    fn rotate(
+        prior: &Serder,
+        sn: u128,
+        signers: &[&Signer],
+        next: &[&Signer],
+        br: &[&Signer],
+        ba: &[&Signer],
+        bt: &str,
+    ) -> Serder {
+        saidify(
+            json!({
+                "v": "", "t": "rot", "d": "", "i": prior.pre().unwrap(), "s": format!("{sn:x}"),
+                "p": prior.said().unwrap(), "kt": format!("{:x}", signers.len()), "k": keys(signers),
+                "nt": format!("{:x}", next.len()), "n": digests(next),
+                "bt": bt, "br": keys(br), "ba": keys(ba), "a": []
+            }),
+            &["d"],
+        )
+    }
+
+    fn interact(prior: &Serder, sn: u128, seals: JsonValue) -> Serder {
+        saidify(
+            json!({
+                "v": "", "t": "ixn", "d": "", "i": prior.pre().unwrap(), "s": format!("{sn:x}"),
+                "p": prior.said().unwrap(), "a": seals
+            }),
+            &["d"],
+        )
+    }
+
+    fn sigs(serder: &Serder, signers: &[&Signer]) -> CesrGroup {
+        let sigs = signers
+            .iter()
+            .enumerate()
+            .map(|(i, signer)| ControllerIdxSig {
+                siger: signer.sign_indexed(&serder.raw(), false, i as u32, None).unwrap(),
+            })
+            .collect();
+        CesrGroup::ControllerIdxSigsVariant { value: ControllerIdxSigs::new(sigs) }
+    }
+
+    fn wigs(serder: &Serder, witnesses: &[(u32, &Signer)]) -> CesrGroup {
+        let sigs = witnesses
+            .iter()
+            .map(|(i, witness)| WitnessIdxSig {
+                siger: witness.sign_indexed(&serder.raw(), false, *i, None).unwrap(),
+            })
+            .collect();
+        CesrGroup::WitnessIdxSigsVariant { value: WitnessIdxSigs::new(sigs) }
+    }
+
+    fn couple(serder: &Serder) -> SealSourceCouple {
+        SealSourceCouple::new(
+            Seqner::new_with_sn(serder.sn().unwrap()).unwrap(),
+            Saider::new_with_qb64(&serder.said().unwrap()).unwrap(),
+        )
+    }
+
+    fn source(serder: &Serder) -> CesrGroup {
+        CesrGroup::SealSourceCouplesVariant { value: SealSourceCouples::new(vec![couple(serder)]) }
+    }
+
+    #[test]
+    pub fn test_validate_kel() {
+        let (key_0, key_1, key_2) = (signer(0, true), signer(1, true), signer(2, true));
+        let mut kevery = Kevery::new();
+
+        let icp = incept(&[&key_0], &[&key_1], &[], "0");
+        kevery.process(&icp, &[&sigs(&icp, &[&key_0])]).unwrap();
+        let ixn = interact(&icp, 1, json!([]));
+        kevery.process(&ixn, &[&sigs(&ixn, &[&key_0])]).unwrap();
+        let rot = rotate(&ixn, 2, &[&key_1], &[&key_2], &[], &[], "0");
+        kevery.process(&rot, &[&sigs(&rot, &[&key_1])]).unwrap();
+        // events accepted before are accepted again
+        kevery.process(&ixn, &[&sigs(&ixn, &[&key_0])]).unwrap();
+
+        let kever = kevery.kever(&icp.pre().unwrap()).unwrap();
+        assert_eq!(2, kever.sn());
+        assert_eq!(rot.said().unwrap(), kever.said());
+        assert_eq!(vec![key_1.verfer()], kever.key_state().verfers);
+        assert_eq!(
+            digests(&[&key_2]),
+            kever.digers().iter().map(|d| d.qb64().unwrap()).collect::<Vec<_>>()
+        );
+
+        // signatures refer to the key states of establishment events
+        let provider: &dyn KeyStateProvider = &kevery;
+        let state = provider.establishment(&icp.pre().unwrap(), 0, &icp.said().unwrap()).unwrap();
+        assert_eq!(vec![key_0.verfer()], state.verfers);
+        assert!(provider.establishment(&icp.pre().unwrap(), 1, &ixn.said().unwrap()).is_none());
+    }
+
+    #[test]
+    pub fn test_reject_out_of_order_and_duplicitous_events() {
+        let (key_0, key_1) = (signer(0, true), signer(1, true));
+        let mut kevery = Kevery::new();
+        let icp = incept(&[&key_0], &[&key_1], &[], "0");
+        let ixn = interact(&icp, 1, json!([]));
+        let err = kevery.process(&ixn, &[&sigs(&ixn, &[&key_0])]).unwrap_err();
+        assert!(matches!(err, ParsideError::OutOfOrderEvent(..)));
+
+        kevery.process(&icp, &[&sigs(&icp, &[&key_0])]).unwrap();
+        let ixn_2 = interact(&ixn, 2, json!([]));
+        let err = kevery.process(&ixn_2, &[&sigs(&ixn_2, &[&key_0])]).unwrap_err();
+        assert!(matches!(err, ParsideError::OutOfOrderEvent(..)));
+
+        kevery.process(&ixn, &[&sigs(&ixn, &[&key_0])]).unwrap();
+        let forked = interact(&icp, 1, json!([{"d": icp.said().unwrap()}]));
+        let err = kevery.process(&forked, &[&sigs(&forked, &[&key_0])]).unwrap_err();
+        assert!(matches!(err, ParsideError::DuplicitousEvent(..)));
+        // forks not signed by the controller are invalid rather than duplicitous
+        let err = kevery.process(&forked, &[]).unwrap_err();
+        assert!(matches!(err, ParsideError::InvalidEvent(..)));
+        let err = kevery.process(&forked, &[&sigs(&forked, &[&key_1])]).unwrap_err();
+        assert!(matches!(err, ParsideError::InvalidEvent(..)));
+        kevery.process(&icp, &[&sigs(&icp, &[&key_0])]).unwrap();
+        assert_eq!(ixn.said().unwrap(), kevery.kever(&icp.pre().unwrap()).unwrap().said());
+    }
+
+    #[test]
+    pub fn test_reject_invalid_events() {
+        let (key_0, key_1, key_2) = (signer(0, true), signer(1, true), signer(2, true));
+        let witness = signer(9, false);
+        let mut kevery = Kevery::new();
+        let icp = incept(&[&key_0], &[&key_1], &[&witness], "1");
+        let invalid =
+            |result: ParsideResult<()>| matches!(result, Err(ParsideError::InvalidEvent(..)));
+
+        // unsigned and wrongly signed inceptions
+        assert!(invalid(kevery.process(&icp, &[])));
+        assert!(invalid(
+            kevery.process(&icp, &[&sigs(&icp, &[&key_1]), &wigs(&icp, &[(0, &witness)])])
+        ));
+        kevery.process(&icp, &[&sigs(&icp, &[&key_0]), &wigs(&icp, &[(0, &witness)])]).unwrap();
+
+        // prior digest of another event
+        let ixn = interact(&icp, 1, json!([]));
+        let stray = interact(&ixn, 1, json!([]));
+        assert!(invalid(
+            kevery.process(&stray, &[&sigs(&stray, &[&key_0]), &wigs(&stray, &[(0, &witness)])])
+        ));
+
+        // rotation to keys not committed to by the inception
+        let rot = rotate(&icp, 1, &[&key_2], &[&key_0], &[], &[], "1");
+        assert!(invalid(
+            kevery.process(&rot, &[&sigs(&rot, &[&key_2]), &wigs(&rot, &[(0, &witness)])])
+        ));
+
+        // witness cuts have to be witnesses in force, thresholds have to fit the witnesses
+        let rot = rotate(&icp, 1, &[&key_1], &[&key_2], &[&key_0], &[], "1");
+        assert!(invalid(
+            kevery.process(&rot, &[&sigs(&rot, &[&key_1]), &wigs(&rot, &[(0, &witness)])])
+        ));
+        let rot = rotate(&icp, 1, &[&key_1], &[&key_2], &[&witness], &[], "1");
+        assert!(invalid(kevery.process(&rot, &[&sigs(&rot, &[&key_1])])));
+        let rot = rotate(&icp, 1, &[&key_1], &[&key_2], &[&witness], &[], "0");
+        kevery.process(&rot, &[&sigs(&rot, &[&key_1])]).unwrap();
+        assert!(kevery.kever(&icp.pre().unwrap()).unwrap().witnesses().is_empty());
+
+        // digest has to match the event
+        let ixn = interact(&rot, 2, json!([]));
+        let raw = String::from_utf8(ixn.raw()).unwrap().replace(r#""a":[]"#, r#""a":{}"#);
+        let tampered = Serder::new_with_raw(raw.as_bytes()).unwrap();
+        assert!(invalid(kevery.process(&tampered, &[&sigs(&tampered, &[&key_1])])));
+    }
+
+    #[test]
+    pub fn test_reject_partially_witnessed_events() {
+        let (key_0, key_1) = (signer(0, true), signer(1, true));
+        let (witness_0, witness_1) = (signer(8, false), signer(9, false));
+        let mut kevery = Kevery::new();
+        let icp = incept(&[&key_0], &[&key_1], &[&witness_0, &witness_1], "2");
+        let partially_witnessed =
+            |result: ParsideResult<()>| matches!(result, Err(ParsideError::PartiallyWitnessed(..)));
+
+        assert!(partially_witnessed(kevery.process(&icp, &[&sigs(&icp, &[&key_0])])));
+        assert!(partially_witnessed(
+            kevery.process(&icp, &[&sigs(&icp, &[&key_0]), &wigs(&icp, &[(1, &witness_1)])])
+        ));
+        assert!(kevery.kever(&icp.pre().unwrap()).is_none());
+        let receipts = wigs(&icp, &[(0, &witness_0), (1, &witness_1)]);
+        kevery.process(&icp, &[&sigs(&icp, &[&key_0]), &receipts]).unwrap();
+
+        let ixn = interact(&icp, 1, json!([]));
+        assert!(partially_witnessed(
+            kevery.process(&ixn, &[&sigs(&ixn, &[&key_0]), &wigs(&ixn, &[(0, &witness_0)])])
+        ));
+        assert_eq!(0, kevery.kever(&icp.pre().unwrap()).unwrap().sn());
+        let receipts = wigs(&ixn, &[(0, &witness_0), (1, &witness_1)]);
+        kevery.process(&ixn, &[&sigs(&ixn, &[&key_0]), &receipts]).unwrap();
+        assert_eq!(1, kevery.kever(&icp.pre().unwrap()).unwrap().sn());
+    }
+
+    #[test]
+    pub fn test_validate_witness_additions() {
+        let (key_0, key_1, key_2) = (signer(0, true), signer(1, true), signer(2, true));
+        let (witness_0, witness_1) = (signer(8, false), signer(9, false));
+        let mut kevery = Kevery::new();
+        let icp = incept(&[&key_0], &[&key_1], &[&witness_0], "1");
+        kevery.process(&icp, &[&sigs(&icp, &[&key_0]), &wigs(&icp, &[(0, &witness_0)])]).unwrap();
+
+        // witnesses already in force cannot be added again
+        let rot = rotate(&icp, 1, &[&key_1], &[&key_2], &[], &[&witness_0], "1");
+        assert!(matches!(
+            kevery.process(&rot, &[&sigs(&rot, &[&key_1]), &wigs(&rot, &[(0, &witness_0)])]),
+            Err(ParsideError::InvalidEvent(..))
+        ));
+
+        // added witnesses receipt the rotation adding them
+        let rot = rotate(&icp, 1, &[&key_1], &[&key_2], &[], &[&witness_1], "2");
+        let err = kevery
+            .process(&rot, &[&sigs(&rot, &[&key_1]), &wigs(&rot, &[(0, &witness_0)])])
+            .unwrap_err();
+        assert!(matches!(err, ParsideError::PartiallyWitnessed(..)));
+        let receipts = wigs(&rot, &[(0, &witness_0), (1, &witness_1)]);
+        kevery.process(&rot, &[&sigs(&rot, &[&key_1]), &receipts]).unwrap();
+
+        let kever = kevery.kever(&icp.pre().unwrap()).unwrap();
+        assert_eq!(keys(&[&witness_0, &witness_1]), kever.witnesses());
+        assert_eq!(2, kever.toad());
+    }
+
+    #[test]
+    pub fn test_reject_events_after_abandonment() {
+        let (key_0, key_1) = (signer(0, true), signer(1, true));
+        let mut kevery = Kevery::new();
+        let icp = incept(&[&key_0], &[&key_1], &[], "0");
+        kevery.process(&icp, &[&sigs(&icp, &[&key_0])]).unwrap();
+        let rot = rotate(&icp, 1, &[&key_1], &[], &[], &[], "0");
+        kevery.process(&rot, &[&sigs(&rot, &[&key_1])]).unwrap();
+        assert!(kevery.kever(&icp.pre().unwrap()).unwrap().digers().is_empty());
+
+        let ixn = interact(&rot, 2, json!([]));
+        let err = kevery.process(&ixn, &[&sigs(&ixn, &[&key_1])]).unwrap_err();
+        assert!(matches!(err, ParsideError::InvalidEvent(..)));
+        let rot_2 = rotate(&rot, 2, &[&key_1], &[&key_0], &[], &[], "0");
+        let err = kevery.process(&rot_2, &[&sigs(&rot_2, &[&key_1])]).unwrap_err();
+        assert!(matches!(err, ParsideError::InvalidEvent(..)));
+        assert_eq!(1, kevery.kever(&icp.pre().unwrap()).unwrap().sn());
+    }
+
+    #[test]
+    pub fn test_validate_delegated_kel() {
+        let (key_0, key_1) = (signer(0, true), signer(1, true));
+        let (delegate_0, delegate_1) = (signer(3, true), signer(4, true));
+        let mut kevery = Kevery::new();
+        let icp = incept(&[&key_0], &[&key_1], &[], "0");
+        let delegator = icp.pre().unwrap();
+        let dip = delegated_incept(&delegate_0, &delegate_1, &delegator);
+        let seal = json!([{"i": dip.pre().unwrap(), "s": "0", "d": dip.said().unwrap()}]);
+        let ixn = interact(&icp, 1, seal);
+
+        // delegator not seen, then delegating event not seen
+        let err = kevery.process(&dip, &[&sigs(&dip, &[&delegate_0]), &source(&ixn)]).unwrap_err();
+        assert!(matches!(err, ParsideError::OutOfOrderEvent(..)));
+        kevery.process(&icp, &[&sigs(&icp, &[&key_0])]).unwrap();
+        let err = kevery.process(&dip, &[&sigs(&dip, &[&delegate_0]), &source(&ixn)]).unwrap_err();
+        assert!(matches!(err, ParsideError::OutOfOrderEvent(..)));
+
+        // the delegator has to anchor the delegated event
+        let err = kevery.process(&dip, &[&sigs(&dip, &[&delegate_0]), &source(&icp)]).unwrap_err();
+        assert!(matches!(err, ParsideError::InvalidEvent(..)));
+        kevery.process(&ixn, &[&sigs(&ixn, &[&key_0])]).unwrap();
+        let err = kevery.process(&dip, &[&sigs(&dip, &[&delegate_0])]).unwrap_err();
+        assert!(matches!(err, ParsideError::InvalidEvent(..)));
+        // only a single seal source may be attached
+        let err = kevery
+            .process(&dip, &[&sigs(&dip, &[&delegate_0]), &source(&icp), &source(&ixn)])
+            .unwrap_err();
+        assert!(matches!(err, ParsideError::InvalidEvent(..)));
+        let couples = CesrGroup::SealSourceCouplesVariant {
+            value: SealSourceCouples::new(vec![couple(&ixn), couple(&icp)]),
+        };
+        let err = kevery.process(&dip, &[&sigs(&dip, &[&delegate_0]), &couples]).unwrap_err();
+        assert!(matches!(err, ParsideError::InvalidEvent(..)));
+        kevery.process(&dip, &[&sigs(&dip, &[&delegate_0]), &source(&ixn)]).unwrap();
+        assert_eq!(
+            Some(delegator.as_str()),
+            kevery.kever(&dip.pre().unwrap()).unwrap().delegator()
+        );
+    }
+
+    #[test]
+    pub fn test_process_parsed_stream() {
+        let (key_0, key_1, key_2) = (signer(0, true), signer(1, true), signer(2, true));
+        let witness = signer(9, false);
+        let icp = incept(&[&key_0, &key_1], &[&key_1, &key_2], &[&witness], "1");
+        let rot = rotate(&icp, 1, &[&key_1, &key_2], &[&key_0], &[], &[], "1");
+
+        let mut stream = icp.raw();
+        let attachments = CesrGroup::AttachedMaterialQuadletsVariant {
+            value: crate::message::AttachedMaterialQuadlets::new(vec![
+                sigs(&icp, &[&key_0, &key_1]),
+                wigs(&icp, &[(0, &witness)]),
+            ]),
+        };
+        stream.extend(attachments.qb64b().unwrap());
+        stream.extend(rot.raw());
+        stream.extend(sigs(&rot, &[&key_1, &key_2]).qb64b().unwrap());
+        stream.extend(wigs(&rot, &[(0, &witness)]).qb64b().unwrap());
+
+        let (_, message_list) = MessageList::from_stream_bytes(&stream).unwrap();
+        let mut kevery = Kevery::new();
+        for event in message_list.events() {
+            kevery.process_event(&event.unwrap()).unwrap();
+        }
+        assert_eq!(1, kevery.kever(&icp.pre().unwrap()).unwrap().sn());
+    }
+}
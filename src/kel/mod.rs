@@ -0,0 +1,5 @@
+pub mod kever;
+pub mod kevery;
+
+pub use kever::Kever;
+pub use kevery::Kevery;
@@ -1,5 +1,19 @@
 pub mod error;
+pub mod kel;
 pub mod message;
 mod utils;
+pub mod verify;
 
-pub use message::{CesrGroup, CustomPayload, Group, Message, MessageList};
+pub use kel::{Kever, Kevery};
+pub use message::{
+    annotate, denot, transcode, CesrGroup, CustomPayload, Domain, Event, EventBody, Framing,
+    GenusVersion, Group, Message, MessageList, MessageReader, NativeBody, StreamParser,
+    VersionString,
+};
+#[cfg(feature = "tokio")]
+pub use message::{CesrCodec, MessageStream};
+pub use verify::{
+    verify_controller_sigs, verify_non_trans_receipts, verify_trans_idx_sig_groups,
+    verify_trans_receipts, verify_witness_receipts, verify_witness_sigs, InMemoryKeyStates,
+    KeyState, KeyStateProvider, ReceiptReport, TransSignerReport, VerificationReport,
+};
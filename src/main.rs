@@ -0,0 +1,247 @@
+//! `parside` command-line tool inspecting, converting and validating CESR streams read from a file
+//! or stdin, e.g. KEL exports.
+
+use std::fs;
+use std::io::{self, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use anyhow::Context;
+use clap::{Parser, Subcommand, ValueEnum};
+use parside::error::ParsideError;
+use parside::{annotate, Domain, Message, MessageList, MessageReader};
+
+#[derive(Parser)]
+#[command(name = "parside", version, about = "Inspect, convert and validate CESR streams")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print each message of the stream as a line of JSON
+    Parse {
+        /// Stream file, stdin if omitted
+        input: Option<PathBuf>,
+    },
+    /// Print the stream with every counter and primitive explained on its own line
+    Annotate {
+        /// Stream file, stdin if omitted
+        input: Option<PathBuf>,
+    },
+    /// Convert attachments and native bodies of the stream to text or binary domain
+    Convert {
+        /// Domain to convert to
+        #[arg(long, value_enum)]
+        to: Target,
+        /// Stream file, stdin if omitted
+        input: Option<PathBuf>,
+        /// Output file, stdout if omitted
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Write each event, message body together with its attachments, to a file of its own
+    Split {
+        /// Stream file, stdin if omitted
+        input: Option<PathBuf>,
+        /// Directory the event files are written to
+        #[arg(short, long, default_value = ".")]
+        out_dir: PathBuf,
+    },
+    /// Check the stream is well-formed, printing the offset of the first malformed byte otherwise
+    Check {
+        /// Stream file, stdin if omitted
+        input: Option<PathBuf>,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Target {
+    /// Qualified Base64, qb64
+    Text,
+    /// Qualified binary, qb2
+    Binary,
+}
+
+impl From<Target> for Domain {
+    fn from(target: Target) -> Self {
+        match target {
+            Target::Text => Domain::Text,
+            Target::Binary => Domain::Binary,
+        }
+    }
+}
+
+fn main() -> anyhow::Result<ExitCode> {
+    match Cli::parse().command {
+        Command::Parse { input } => parse(input),
+        Command::Annotate { input } => {
+            print!("{}", annotate(&read_input(input)?)?);
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Convert { to, input, output } => convert(input, output, to.into()),
+        Command::Split { input, out_dir } => split(input, out_dir),
+        Command::Check { input } => check(input),
+    }
+}
+
+fn open_input(input: Option<PathBuf>) -> anyhow::Result<Box<dyn Read>> {
+    Ok(match input {
+        Some(path) => Box::new(
+            fs::File::open(&path).with_context(|| format!("Failed to open {}", path.display()))?,
+        ),
+        None => Box::new(io::stdin().lock()),
+    })
+}
+
+fn read_input(input: Option<PathBuf>) -> anyhow::Result<Vec<u8>> {
+    let mut bytes = vec![];
+    open_input(input)?.read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn parse(input: Option<PathBuf>) -> anyhow::Result<ExitCode> {
+    let mut out = BufWriter::new(io::stdout().lock());
+    for message in MessageReader::new(open_input(input)?) {
+        serde_json::to_writer(&mut out, &message?)?;
+        writeln!(out)?;
+    }
+    out.flush()?;
+    Ok(ExitCode::SUCCESS)
+}
+
+fn convert(
+    input: Option<PathBuf>,
+    output: Option<PathBuf>,
+    domain: Domain,
+) -> anyhow::Result<ExitCode> {
+    let converted = parside::transcode(&read_input(input)?, domain)?;
+    match output {
+        Some(path) => fs::write(&path, converted)
+            .with_context(|| format!("Failed to write {}", path.display()))?,
+        None => io::stdout().lock().write_all(&converted)?,
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn split(input: Option<PathBuf>, out_dir: PathBuf) -> anyhow::Result<ExitCode> {
+    fs::create_dir_all(&out_dir)?;
+    let mut index = 0;
+    let mut event: Vec<Message> = vec![];
+    for message in MessageReader::new(open_input(input)?) {
+        match message? {
+            Message::Group { .. } if event.is_empty() => {
+                anyhow::bail!("Attachments without preceding message body")
+            }
+            message @ Message::Group { .. } => event.push(message),
+            // the event is complete once the next message body starts
+            message => {
+                if !event.is_empty() {
+                    write_event(&out_dir, index, std::mem::take(&mut event))?;
+                    index += 1;
+                }
+                event.push(message);
+            }
+        }
+    }
+    if !event.is_empty() {
+        write_event(&out_dir, index, event)?;
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Write message body and attachments of the `index`th event to a file of its own
+fn write_event(out_dir: &Path, index: usize, messages: Vec<Message>) -> anyhow::Result<()> {
+    let name = match ilk(&messages[0]) {
+        Some(ilk) => format!("{index:06}-{ilk}.cesr"),
+        None => format!("{index:06}.cesr"),
+    };
+    // each file starts with the genus/version counter of its event, if any
+    let qb64 = MessageList { messages }.to_qb64()?;
+    let path = out_dir.join(name);
+    fs::write(&path, qb64).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Message type of a message body, e.g. `icp`
+fn ilk(message: &Message) -> Option<String> {
+    match message {
+        Message::Custom { value, .. } => value.value["t"].as_str().map(str::to_string),
+        Message::Native { value, .. } => value.ilk(),
+        _ => None,
+    }
+}
+
+fn check(input: Option<PathBuf>) -> anyhow::Result<ExitCode> {
+    check_stream(open_input(input)?, &mut io::stdout().lock(), &mut io::stderr().lock())
+}
+
+/// Parse every message read from `reader`, printing the number of messages to `out`, or the
+/// first error with the offset it was raised at to `err`
+fn check_stream(
+    reader: impl Read,
+    out: &mut impl Write,
+    err: &mut impl Write,
+) -> anyhow::Result<ExitCode> {
+    let mut messages = MessageReader::new(reader);
+    let mut count = 0;
+    while let Some(message) = messages.next() {
+        if let Err(error) = message {
+            // errors outside of groups are located at the start of the failing message
+            let offset = error.offset().unwrap_or(messages.consumed());
+            let error = match error {
+                ParsideError::Incomplete(needed) => {
+                    format!("stream ends mid-message, needing at least {needed} more bytes")
+                }
+                error => error.to_string(),
+            };
+            writeln!(err, "error at offset {offset}: {error}")?;
+            return Ok(ExitCode::FAILURE);
+        }
+        count += 1;
+    }
+    writeln!(out, "ok: {count} messages")?;
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(test)]
+#[allow(dead_code)]
+#[path = "message/groups/fixtures.rs"]
+mod fixtures;
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use fixtures::{CONTROLLER_IDX_SIGS, PAYLOAD};
+
+    fn check_bytes(stream: &[u8]) -> (ExitCode, String, String) {
+        let (mut out, mut err) = (vec![], vec![]);
+        let code = check_stream(stream, &mut out, &mut err).unwrap();
+        (code, String::from_utf8(out).unwrap(), String::from_utf8(err).unwrap())
+    }
+
+    #[test]
+    pub fn test_check_stream() {
+        let stream = [PAYLOAD, CONTROLLER_IDX_SIGS, PAYLOAD].concat();
+        let (code, out, err) = check_bytes(&stream);
+        assert_eq!(ExitCode::SUCCESS, code);
+        assert_eq!("ok: 3 messages\n", out);
+        assert!(err.is_empty());
+    }
+
+    #[test]
+    pub fn test_check_malformed_stream() {
+        let mut sigs = CONTROLLER_IDX_SIGS.to_vec();
+        sigs[10] = b'#';
+        let stream = [PAYLOAD, &sigs].concat();
+        let (code, out, err) = check_bytes(&stream);
+        assert_eq!(ExitCode::FAILURE, code);
+        assert!(out.is_empty());
+        assert!(err.starts_with(&format!("error at offset {}: ", PAYLOAD.len() + 4)));
+
+        let stream = [PAYLOAD, &CONTROLLER_IDX_SIGS[..20]].concat();
+        let (code, _, err) = check_bytes(&stream);
+        assert_eq!(ExitCode::FAILURE, code);
+        assert!(err.starts_with(&format!("error at offset {}: stream ends", PAYLOAD.len())));
+    }
+}
@@ -0,0 +1,430 @@
+use cesride::{Indexer, Matter};
+
+use crate::error::{ParsideError, ParsideResult};
+use crate::message::counter::Counter;
+use crate::message::custom_payload::CustomPayload;
+use crate::message::genus_version::GenusVersion;
+use crate::message::native_body::{NativeBody, NativeField, NativeValue};
+use crate::message::primitive::Primitive;
+use crate::message::{CesrGroup, Group, SadPathSigs};
+use crate::{Message, MessageList};
+
+/// Separator of annotated material and its annotation
+const SEPARATOR: &str = " # ";
+/// Indentation of material nested in a group
+const INDENT: &str = "  ";
+
+/// Names of the primitive codes of the cesride matter table, and of the CESR 2.0 tag codes
+const MATTER_NAMES: [(&str, &str); 56] = [
+    ("A", "Ed25519_Seed"),
+    ("B", "Ed25519N"),
+    ("C", "X25519"),
+    ("D", "Ed25519"),
+    ("E", "Blake3_256"),
+    ("F", "Blake2b_256"),
+    ("G", "Blake2s_256"),
+    ("H", "SHA3_256"),
+    ("I", "SHA2_256"),
+    ("J", "ECDSA_256k1_Seed"),
+    ("K", "Ed448_Seed"),
+    ("L", "X448"),
+    ("M", "Short"),
+    ("N", "Big"),
+    ("O", "X25519_Private"),
+    ("P", "X25519_Cipher_Seed"),
+    ("Q", "ECDSA_256r1_Seed"),
+    ("X", "Tag3"),
+    ("Y", "Tag7"),
+    ("0A", "Salt_128"),
+    ("0B", "Ed25519_Sig"),
+    ("0C", "ECDSA_256k1_Sig"),
+    ("0D", "Blake3_512"),
+    ("0E", "Blake2b_512"),
+    ("0F", "SHA3_512"),
+    ("0G", "SHA2_512"),
+    ("0H", "Long"),
+    ("0I", "ECDSA_256r1_Sig"),
+    ("0J", "Tag1"),
+    ("0K", "Tag2"),
+    ("1AAA", "ECDSA_256k1N"),
+    ("1AAB", "ECDSA_256k1"),
+    ("1AAC", "Ed448N"),
+    ("1AAD", "Ed448"),
+    ("1AAE", "Ed448_Sig"),
+    ("1AAF", "Tern"),
+    ("1AAG", "DateTime"),
+    ("1AAH", "X25519_Cipher_Salt"),
+    ("1AAI", "ECDSA_256r1N"),
+    ("1AAJ", "ECDSA_256r1"),
+    ("2AAA", "TBD1"),
+    ("3AAA", "TBD2"),
+    ("4A", "StrB64_L0"),
+    ("5A", "StrB64_L1"),
+    ("6A", "StrB64_L2"),
+    ("7AAA", "StrB64_Big_L0"),
+    ("8AAA", "StrB64_Big_L1"),
+    ("9AAA", "StrB64_Big_L2"),
+    ("4B", "Bytes_L0"),
+    ("5B", "Bytes_L1"),
+    ("6B", "Bytes_L2"),
+    ("7AAB", "Bytes_Big_L0"),
+    ("8AAB", "Bytes_Big_L1"),
+    ("9AAB", "Bytes_Big_L2"),
+    ("1__-", "Escape"),
+    ("1___", "Stream"),
+];
+
+/// Names of the indexed signature codes of the cesride indexer table
+const INDEXER_NAMES: [(&str, &str); 16] = [
+    ("A", "Ed25519"),
+    ("B", "Ed25519_Crt"),
+    ("C", "ECDSA_256k1"),
+    ("D", "ECDSA_256k1_Crt"),
+    ("E", "ECDSA_256r1"),
+    ("F", "ECDSA_256r1_Crt"),
+    ("0A", "Ed448"),
+    ("0B", "Ed448_Crt"),
+    ("2A", "Ed25519_Big"),
+    ("2B", "Ed25519_Big_Crt"),
+    ("2C", "ECDSA_256k1_Big"),
+    ("2D", "ECDSA_256k1_Big_Crt"),
+    ("2E", "ECDSA_256r1_Big"),
+    ("2F", "ECDSA_256r1_Big_Crt"),
+    ("3A", "Ed448_Big"),
+    ("3B", "Ed448_Big_Crt"),
+];
+
+/// Names of the native body counter codes of the CESR 2.0 table
+const NATIVE_NAMES: [(&str, &str); 4] = [
+    ("-F", "FixBodyGroup"),
+    ("-G", "MapBodyGroup"),
+    ("-I", "GenericMapGroup"),
+    ("-J", "GenericListGroup"),
+];
+
+/// Explain a stream line by line, similar to keripy's `annotate`. Every message body, counter and
+/// primitive is written on its own line in text domain, followed by its code name and, for
+/// counters, its count. Material nested in groups is indented. Custom payloads have to be single
+/// lines of text to be annotated. See [`denot`] for the reverse.
+pub fn annotate(bytes: &[u8]) -> ParsideResult<String> {
+    let message_list = MessageList::from_complete_stream_bytes(bytes)?;
+    let mut annotator = Annotator::default();
+    let mut version = GenusVersion::default();
+    for message in &message_list.messages {
+        let mut counters = message.counters().to_vec();
+        if counters.is_empty() && message.version() != &version {
+            counters.push(message.version().counter()?);
+        }
+        for counter in counters {
+            version = GenusVersion::from_counter(&counter)?.unwrap_or(version);
+            annotator.line(0, &counter.qb64()?, &format!("GenusVersion {version}"));
+        }
+        match message {
+            Message::Custom { value, .. } => annotator.payload(value)?,
+            Message::Native { value, .. } => annotator.native_body(value)?,
+            Message::Group { value, version, .. } => annotator.group(0, value, version)?,
+        }
+    }
+    Ok(annotator.out)
+}
+
+/// Strip the annotations of [`annotate`] output, giving back the annotated stream in text domain
+pub fn denot(annotated: &str) -> String {
+    annotated
+        .lines()
+        .map(|line| {
+            let line = line.trim_start();
+            line.rfind(SEPARATOR).map_or(line, |at| &line[..at])
+        })
+        .collect()
+}
+
+#[derive(Default)]
+struct Annotator {
+    out: String,
+}
+
+impl Annotator {
+    fn line(&mut self, depth: usize, material: &str, annotation: &str) {
+        self.out.push_str(&INDENT.repeat(depth));
+        self.out.push_str(material);
+        self.out.push_str(SEPARATOR);
+        self.out.push_str(annotation);
+        self.out.push('\n');
+    }
+
+    fn payload(&mut self, payload: &CustomPayload) -> ParsideResult<()> {
+        let text = std::str::from_utf8(&payload.raw)
+            .ok()
+            .filter(|text| !text.contains(['\n', '\r']))
+            .ok_or_else(|| {
+                ParsideError::Unexpected(format!(
+                    "Body of {} bytes is not a single line of text",
+                    payload.raw.len()
+                ))
+            })?;
+        let annotation = match &payload.version {
+            Some(version) => format!("{} {} body", version.protocol, version.kind),
+            None => "Custom body".to_string(),
+        };
+        self.line(0, text, &annotation);
+        Ok(())
+    }
+
+    fn counter(&mut self, depth: usize, counter: &Counter, name: &str) -> ParsideResult<()> {
+        self.line(depth, &counter.qb64()?, &format!("{name} count={}", counter.count()));
+        Ok(())
+    }
+
+    fn matter(&mut self, depth: usize, matter: &impl Matter) -> ParsideResult<()> {
+        self.line(depth, &matter.qb64()?, name(&MATTER_NAMES, &matter.code()));
+        Ok(())
+    }
+
+    fn indexer(&mut self, depth: usize, indexer: &impl Indexer) -> ParsideResult<()> {
+        let annotation =
+            format!("{} index={}", name(&INDEXER_NAMES, &indexer.code()), indexer.index());
+        self.line(depth, &indexer.qb64()?, &annotation);
+        Ok(())
+    }
+
+    fn group(
+        &mut self,
+        depth: usize,
+        group: &CesrGroup,
+        version: &GenusVersion,
+    ) -> ParsideResult<()> {
+        let counter = Counter::new_with_qb64b(&group.qb64b_with_version(version)?)?;
+        let small_code = counter.small_code();
+        let name = version.group_code(&small_code).and_then(CesrGroup::group_name).unwrap_or("");
+        self.counter(depth, &counter, name)?;
+
+        let depth = depth + 1;
+        match group {
+            CesrGroup::ControllerIdxSigsVariant { value } => {
+                for item in value.value() {
+                    self.indexer(depth, &item.siger)?;
+                }
+            }
+            CesrGroup::WitnessIdxSigsVariant { value } => {
+                for item in value.value() {
+                    self.indexer(depth, &item.siger)?;
+                }
+            }
+            CesrGroup::NonTransReceiptCouplesVariant { value } => {
+                for item in value.value() {
+                    self.matter(depth, &item.cigar.verfer())?;
+                    self.matter(depth, &item.cigar)?;
+                }
+            }
+            CesrGroup::TransReceiptQuadruplesVariant { value } => {
+                for item in value.value() {
+                    self.matter(depth, &item.prefixer)?;
+                    self.matter(depth, &item.seqner)?;
+                    self.matter(depth, &item.saider)?;
+                    self.indexer(depth, &item.siger)?;
+                }
+            }
+            CesrGroup::TransIdxSigGroupsVariant { value } => {
+                for item in value.value() {
+                    self.matter(depth, &item.prefixer)?;
+                    self.matter(depth, &item.seqner)?;
+                    self.matter(depth, &item.saider)?;
+                    let isigers =
+                        CesrGroup::ControllerIdxSigsVariant { value: item.isigers.clone() };
+                    self.group(depth, &isigers, version)?;
+                }
+            }
+            CesrGroup::TransLastIdxSigGroupsVariant { value } => {
+                for item in value.value() {
+                    self.matter(depth, &item.prefixer)?;
+                    let isigers =
+                        CesrGroup::ControllerIdxSigsVariant { value: item.isigers.clone() };
+                    self.group(depth, &isigers, version)?;
+                }
+            }
+            CesrGroup::FirstSeenReplayCouplesVariant { value } => {
+                for item in value.value() {
+                    self.matter(depth, &item.firner)?;
+                    self.matter(depth, &item.dater)?;
+                }
+            }
+            CesrGroup::SealSourceCouplesVariant { value } => {
+                for item in value.value() {
+                    self.matter(depth, &item.seqner)?;
+                    self.matter(depth, &item.saider)?;
+                }
+            }
+            CesrGroup::SealSourceTriplesVariant { value } => {
+                for item in value.value() {
+                    self.matter(depth, &item.prefixer)?;
+                    self.matter(depth, &item.seqner)?;
+                    self.matter(depth, &item.saider)?;
+                }
+            }
+            CesrGroup::AttachedMaterialQuadletsVariant { value } => {
+                for group in value.value() {
+                    self.group(depth, group, version)?;
+                }
+            }
+            CesrGroup::SadPathSigGroupVariant { value } => {
+                self.matter(depth, &value.root)?;
+                let counter = Counter::new_with_code_and_count(SadPathSigs::CODE, 1)?;
+                for item in value.value() {
+                    self.counter(depth, &counter, "SadPathSigs")?;
+                    self.matter(depth + 1, &item.pather)?;
+                    self.group(depth + 1, &item.sigs, version)?;
+                }
+            }
+            CesrGroup::SadPathSigVariant { value } => {
+                for item in value.value() {
+                    self.matter(depth, &item.pather)?;
+                    self.counter(depth, &item.tcounter, "TransIdxSigGroups")?;
+                    self.matter(depth, &item.prefixer)?;
+                    self.matter(depth, &item.seqner)?;
+                    self.matter(depth, &item.saider)?;
+                    let sigers = CesrGroup::ControllerIdxSigsVariant { value: item.sigers.clone() };
+                    self.group(depth, &sigers, version)?;
+                }
+            }
+            CesrGroup::PathedMaterialQuadletsVariant { value } => {
+                self.matter(depth, &value.pather)?;
+                for group in value.value() {
+                    self.group(depth, group, version)?;
+                }
+            }
+            CesrGroup::EssrPayloadsVariant { value } => {
+                for item in value.value() {
+                    self.matter(depth, &item.texter)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn native_body(&mut self, body: &NativeBody) -> ParsideResult<()> {
+        let counter = Counter::new_with_qb64b(&body.qb64b()?)?;
+        self.counter(0, &counter, name(&NATIVE_NAMES, &counter.small_code()))?;
+        for field in &body.fields {
+            self.native_field(1, field)?;
+        }
+        Ok(())
+    }
+
+    fn native_field(&mut self, depth: usize, field: &NativeField) -> ParsideResult<()> {
+        if let Some(labeler) = &field.labeler {
+            self.primitive(depth, labeler, "label")?;
+        }
+        self.native_value(depth, &field.value, &field.label)
+    }
+
+    fn native_value(
+        &mut self,
+        depth: usize,
+        value: &NativeValue,
+        label: &str,
+    ) -> ParsideResult<()> {
+        let primitive = match value {
+            NativeValue::Primitive { value } => return self.primitive(depth, value, label),
+            NativeValue::List { .. } | NativeValue::Map { .. } => value,
+        };
+        let mut qb64b = vec![];
+        primitive.write_qb64b(&mut qb64b)?;
+        let counter = Counter::new_with_qb64b(&qb64b)?;
+        let annotation = format!("{label} {}", name(&NATIVE_NAMES, &counter.small_code()));
+        self.counter(depth, &counter, &annotation)?;
+        match value {
+            NativeValue::List { value, .. } => {
+                for value in value {
+                    self.native_value(depth + 1, value, label)?;
+                }
+            }
+            NativeValue::Map { value, .. } => {
+                for field in value {
+                    self.native_field(depth + 1, field)?;
+                }
+            }
+            NativeValue::Primitive { .. } => {}
+        }
+        Ok(())
+    }
+
+    fn primitive(&mut self, depth: usize, primitive: &Primitive, label: &str) -> ParsideResult<()> {
+        let annotation = format!("{label} {}", name(&MATTER_NAMES, &primitive.code()));
+        self.line(depth, &primitive.qb64(), &annotation);
+        Ok(())
+    }
+}
+
+/// Name of `code` in `names`, the code itself if it has no name
+fn name<'a>(names: &[(&str, &'a str)], code: &'a str) -> &'a str {
+    names.iter().find(|(named, _)| *named == code).map_or(code, |(_, name)| name)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::message::groups::tests::{
+        ATTACHED_MATERIAL_QUADLETS, KERI_EVENT, TRANS_IDX_SIG_GROUPS,
+    };
+    use crate::transcode;
+    use crate::Domain;
+
+    #[test]
+    pub fn test_annotate_stream() {
+        let stream = [KERI_EVENT, TRANS_IDX_SIG_GROUPS].concat();
+        let annotated = annotate(&stream).unwrap();
+        let lines = annotated.lines().collect::<Vec<_>>();
+        assert_eq!(
+            format!("{} # KERI JSON body", std::str::from_utf8(KERI_EVENT).unwrap()),
+            lines[0]
+        );
+        assert_eq!("-FAB # TransIdxSigGroups count=1", lines[1]);
+        assert!(lines[2].starts_with("  EFhg") && lines[2].ends_with(" # Blake3_256"));
+        assert!(lines[4].ends_with(" # Blake3_256"));
+        assert_eq!("  -AAB # ControllerIdxSigs count=1", lines[5]);
+        assert!(lines[6].starts_with("    AAD") && lines[6].ends_with(" # Ed25519 index=0"));
+        assert_eq!(stream, denot(&annotated).as_bytes());
+    }
+
+    #[test]
+    pub fn test_annotate_nested_and_binary_groups() {
+        let annotated = annotate(ATTACHED_MATERIAL_QUADLETS).unwrap();
+        assert!(annotated.starts_with("-VA- # AttachedMaterialQuadlets count=62\n  -AAB #"));
+        assert!(annotated.contains("\n  -EAB # FirstSeenReplayCouples count=1\n"));
+        assert!(annotated.contains(" # DateTime\n"));
+        assert_eq!(ATTACHED_MATERIAL_QUADLETS, denot(&annotated).as_bytes());
+
+        // binary streams are annotated in text domain
+        let binary = transcode(ATTACHED_MATERIAL_QUADLETS, Domain::Binary).unwrap();
+        assert_eq!(annotated, annotate(&binary).unwrap());
+    }
+
+    #[test]
+    pub fn test_annotate_cesr_2_stream() {
+        let body = "-GAO0J_tXixn0J_dEADZ055vgh5utgSY3OOL1lW0m1pJ1W0Ia6-SVuGa0OqE";
+        let stream = format!("-_AAACAA{body}");
+        let annotated = annotate(stream.as_bytes()).unwrap();
+        let lines = annotated.lines().collect::<Vec<_>>();
+        assert_eq!("-_AAACAA # GenusVersion AAA 2.0", lines[0]);
+        assert_eq!("-GAO # MapBodyGroup count=14", lines[1]);
+        assert_eq!("  0J_t # label Tag1", lines[2]);
+        assert_eq!("  Xixn # t Tag3", lines[3]);
+        assert_eq!(stream, denot(&annotated));
+
+        // genus/version counters are annotated as they are, even when not switching tables
+        let stream = format!("--AAABAA-_AAACAA-_AAACAA{body}");
+        let annotated = annotate(stream.as_bytes()).unwrap();
+        let lines = annotated.lines().collect::<Vec<_>>();
+        assert_eq!("--AAABAA # GenusVersion AAA 1.0", lines[0]);
+        assert_eq!("-_AAACAA # GenusVersion AAA 2.0", lines[2]);
+        assert_eq!(stream, denot(&annotated));
+    }
+
+    #[test]
+    pub fn test_annotate_malformed_stream() {
+        assert!(annotate(b"-FAB####").is_err());
+        let multiline = "{\"v\":\"1\",\n\"t\":\"foo\"}";
+        assert!(annotate(multiline.as_bytes()).is_err());
+    }
+}
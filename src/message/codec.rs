@@ -0,0 +1,223 @@
+use std::io::Write;
+
+use bytes::{Buf, BufMut, BytesMut};
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::error::{ParsideError, ParsideResult};
+use crate::message::custom_payload::Framing;
+use crate::message::domain::Domain;
+use crate::message::genus_version::GenusVersion;
+use crate::message::message::Message;
+use crate::message::stream_parser::MAX_BUFFERED;
+
+/// Codec framing CESR messages, e.g. of KERI direct mode connections with
+/// `Framed<TcpStream, CesrCodec>`. Genus/version counters of decoded bytes select the tables
+/// following messages are parsed with. Encoded messages are written with the genus/version
+/// counters they were read with, messages without such counters are preceded by a genus/version
+/// counter whenever their tables differ from those of the message encoded before.
+#[derive(Debug)]
+pub struct CesrCodec {
+    framing: Framing,
+    domain: Domain,
+    max_buffered: usize,
+    /// Code tables of the last decoded message
+    version: GenusVersion,
+    /// Code tables of the last encoded message
+    encoded_version: GenusVersion,
+    /// Number of bytes decoded into messages so far
+    consumed: usize,
+    needed: usize,
+}
+
+impl Default for CesrCodec {
+    fn default() -> Self {
+        Self::with_framing(Framing::default())
+    }
+}
+
+impl CesrCodec {
+    /// Codec constructor
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Codec finding the end of custom payloads with `framing`
+    pub fn with_framing(framing: Framing) -> Self {
+        Self {
+            framing,
+            domain: Domain::default(),
+            max_buffered: MAX_BUFFERED,
+            version: GenusVersion::default(),
+            encoded_version: GenusVersion::default(),
+            consumed: 0,
+            needed: 0,
+        }
+    }
+
+    /// Encode attachments and native bodies in `domain`
+    pub fn with_domain(mut self, domain: Domain) -> Self {
+        self.domain = domain;
+        self
+    }
+
+    /// Limit bytes buffered for a single message, larger messages fail with
+    /// [`ParsideError::BufferLimit`]
+    pub fn with_max_buffered(mut self, max_buffered: usize) -> Self {
+        self.max_buffered = max_buffered;
+        self
+    }
+}
+
+impl Decoder for CesrCodec {
+    type Item = Message;
+    type Error = ParsideError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> ParsideResult<Option<Message>> {
+        if src.is_empty() {
+            return Ok(None);
+        }
+
+        match Message::from_stream_bytes_with_version(src, self.framing, &self.version) {
+            Ok((rest, message)) => {
+                let consumed = src.len() - rest.len();
+                src.advance(consumed);
+                self.consumed += consumed;
+                self.needed = 0;
+                self.version = message.version().clone();
+                Ok(Some(message))
+            }
+            Err(ParsideError::Incomplete(needed)) => {
+                if src.len() + needed > self.max_buffered {
+                    return Err(ParsideError::BufferLimit(self.max_buffered));
+                }
+                self.needed = needed;
+                src.reserve(needed);
+                Ok(None)
+            }
+            Err(err) => Err(err.shifted(self.consumed)),
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> ParsideResult<Option<Message>> {
+        match self.decode(src)? {
+            Some(message) => Ok(Some(message)),
+            None if src.is_empty() => Ok(None),
+            None => Err(ParsideError::Incomplete(self.needed.max(1))),
+        }
+    }
+}
+
+impl Encoder<Message> for CesrCodec {
+    type Error = ParsideError;
+
+    fn encode(&mut self, item: Message, dst: &mut BytesMut) -> ParsideResult<()> {
+        let mut writer = dst.writer();
+        // counters read with the message are written back by `Message::write_to`
+        if item.counters().is_empty() && item.version() != &self.encoded_version {
+            let counter = item.version().counter()?;
+            let bytes = match self.domain {
+                Domain::Text => counter.qb64b()?,
+                Domain::Binary => counter.qb2()?,
+            };
+            writer.write_all(&bytes)?;
+        }
+        self.encoded_version = item.version().clone();
+        item.write_to(&mut writer, self.domain)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::message::groups::tests::{CONTROLLER_IDX_SIGS, NON_TRANS_RECEIPT_COUPLES, PAYLOAD};
+    use crate::CesrGroup;
+    use futures_util::{SinkExt, StreamExt};
+    use tokio_util::codec::Framed;
+
+    #[test]
+    pub fn test_decode_partial_input() {
+        let mut codec = CesrCodec::new();
+        let mut src = BytesMut::from(PAYLOAD);
+        src.extend_from_slice(&NON_TRANS_RECEIPT_COUPLES[..50]);
+        assert!(matches!(codec.decode(&mut src).unwrap(), Some(Message::Custom { .. })));
+        assert!(codec.decode(&mut src).unwrap().is_none());
+        assert_eq!(50, src.len());
+
+        src.extend_from_slice(&NON_TRANS_RECEIPT_COUPLES[50..]);
+        assert!(codec.decode(&mut src).unwrap().is_some());
+        assert!(src.is_empty());
+
+        src.extend_from_slice(b"-CAB");
+        assert!(matches!(codec.decode_eof(&mut src), Err(ParsideError::Incomplete(..))));
+    }
+
+    #[test]
+    pub fn test_decode_encode_round_trip() {
+        let native = b"-GAO0J_tXixn0J_dEADZ055vgh5utgSY3OOL1lW0m1pJ1W0Ia6-SVuGa0OqE";
+        let stream = [
+            b"-_AAACAA",
+            &native[..],
+            b"-KAW",
+            &CONTROLLER_IDX_SIGS[4..],
+            b"--AAABAA",
+            PAYLOAD,
+            NON_TRANS_RECEIPT_COUPLES,
+        ]
+        .concat();
+
+        let mut codec = CesrCodec::new();
+        let mut src = BytesMut::from(&stream[..]);
+        let mut dst = BytesMut::new();
+        while let Some(message) = codec.decode_eof(&mut src).unwrap() {
+            codec.encode(message, &mut dst).unwrap();
+        }
+        assert_eq!(stream, dst);
+    }
+
+    #[test]
+    pub fn test_encode_switches_tables_of_built_messages() {
+        let stream = [b"-_AAACAA", PAYLOAD, PAYLOAD].concat();
+        let mut decoder = CesrCodec::new();
+        let mut src = BytesMut::from(&stream[..]);
+        let mut first = decoder.decode(&mut src).unwrap().unwrap();
+        let second = decoder.decode(&mut src).unwrap().unwrap();
+        if let Message::Custom { counters, .. } = &mut first {
+            counters.clear();
+        }
+
+        let mut encoder = CesrCodec::new();
+        let mut dst = BytesMut::new();
+        encoder.encode(first, &mut dst).unwrap();
+        encoder.encode(second, &mut dst).unwrap();
+        assert_eq!(stream, dst);
+    }
+
+    #[tokio::test]
+    pub async fn test_framed_duplex_stream() {
+        let (client, server) = tokio::io::duplex(64);
+        let mut client = Framed::new(client, CesrCodec::new());
+        let mut server = Framed::new(server, CesrCodec::new());
+
+        let stream = [PAYLOAD, NON_TRANS_RECEIPT_COUPLES].concat();
+        let (_, message_list) = crate::MessageList::from_stream_bytes(&stream).unwrap();
+        let sent = message_list.messages.len();
+        let writer = tokio::spawn(async move {
+            for message in message_list.messages {
+                client.send(message).await.unwrap();
+            }
+        });
+
+        let mut received = vec![];
+        for _ in 0..sent {
+            received.push(server.next().await.unwrap().unwrap());
+        }
+        writer.await.unwrap();
+        assert!(server.next().await.is_none());
+
+        assert_eq!(PAYLOAD, received[0].payload().unwrap().raw);
+        assert!(matches!(
+            received[1],
+            Message::Group { value: CesrGroup::NonTransReceiptCouplesVariant { .. }, .. }
+        ));
+    }
+}
@@ -25,6 +25,16 @@ pub(crate) enum ColdCode {
     CtOpB2 = 0b111,
 }
 
+impl ColdCode {
+    /// Size of a quadlet (four Base64 characters) in this serialization
+    pub(crate) fn quadlet_size(&self) -> usize {
+        match self {
+            ColdCode::CtOpB2 => 3,
+            _ => 4,
+        }
+    }
+}
+
 impl TryFrom<u8> for ColdCode {
     type Error = ParsideError;
 
@@ -0,0 +1,319 @@
+use crate::error::{ParsideError, ParsideResult};
+use crate::message::genus_version::GenusVersion;
+
+/// Counter codes of the KERI 1.0 table, cesride codex extended with the ESSR payload group and
+/// the big codes of the table missing from cesride. Only material quadlet and ESSR payload groups
+/// have big codes.
+#[allow(non_snake_case)]
+#[allow(non_upper_case_globals)]
+pub mod Codex {
+    pub use cesride::counter::Codex::*;
+
+    pub const ESSRPayloadGroup: &str = "-Z"; // Encrypt sender sign receiver payload, Texter of the cipher
+    pub const BigPathedMaterialQuadlets: &str = "-0L"; // Composed Grouped Pathed Material Quadlet (4 char each)
+    pub const BigESSRPayloadGroup: &str = "-0Z"; // Encrypt sender sign receiver payload, Texter of the cipher
+}
+
+/// Counter codes of the CESR 2.0 table, counts of all groups are in quadlets. Every group code
+/// `-X` has a big variant `-0X`.
+#[allow(non_snake_case)]
+#[allow(non_upper_case_globals)]
+pub mod CodexV2 {
+    pub const GenericGroup: &str = "-A"; // Generic group of groups or primitives
+    pub const BodyWithAttachmentGroup: &str = "-B"; // Message body plus its attachments
+    pub const AttachmentGroup: &str = "-C"; // Attachments of a message body
+    pub const DatagramSegmentGroup: &str = "-D"; // Segment of a datagram
+    pub const ESSRWrapperGroup: &str = "-E"; // Encrypt sender sign receiver wrapper
+    pub const FixBodyGroup: &str = "-F"; // Fixed field message body
+    pub const MapBodyGroup: &str = "-G"; // Field map message body
+    pub const NonNativeBodyGroup: &str = "-H"; // Message body in a non-native serialization
+    pub const GenericMapGroup: &str = "-I"; // Generic field map
+    pub const GenericListGroup: &str = "-J"; // Generic list
+    pub const ControllerIdxSigs: &str = "-K"; // Qualified Base64 Indexed Signature
+    pub const WitnessIdxSigs: &str = "-L"; // Qualified Base64 Indexed Signature
+    pub const NonTransReceiptCouples: &str = "-M"; // Composed Base64 Couple, pre+cig
+    pub const TransReceiptQuadruples: &str = "-N"; // Composed Base64 Quadruple, pre+snu+dig+sig
+    pub const FirstSeenReplayCouples: &str = "-O"; // Composed Base64 Couple, fnu+dts
+    pub const PathedMaterialCouples: &str = "-P"; // Path plus the material at the path
+    pub const DigestSealSingles: &str = "-Q"; // Digest seals
+    pub const MerkleRootSealSingles: &str = "-R"; // Merkle tree root digest seals
+    pub const SealSourceCouples: &str = "-S"; // Composed Base64 couple, snu+dig of source event
+    pub const SealSourceTriples: &str = "-T"; // Composed Base64 triple, pre+snu+dig of source event
+    pub const SealSourceLastSingles: &str = "-U"; // Prefix of the last source event
+    pub const BackerRegistrarSealCouples: &str = "-V"; // Backer registrar seals
+    pub const TypedDigestSealCouples: &str = "-W"; // Typed digest seals
+    pub const TransIdxSigGroups: &str = "-X"; // Composed Base64 Group, pre+snu+dig+ControllerIdxSigs
+    pub const TransLastIdxSigGroups: &str = "-Y"; // Composed Base64 Group, pre+ControllerIdxSigs
+    pub const ESSRPayloadGroup: &str = "-Z"; // Encrypt sender sign receiver payload
+    pub const KERIACDCGenusVersion: &str = "-_AAA"; // KERI ACDC genus and version of its tables
+}
+
+const B64_CHARS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Counter of group items or of material quadlets framing a CESR group.
+/// Unlike `cesride::Counter` it knows the codes of the CESR 2.0 table, big ("-0X#####") variants
+/// included, and converts from `cesride::Counter`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Counter {
+    code: String,
+    count: u32,
+    /// Whether the count is in quadlets rather than in group items, as for all CESR 2.0 groups
+    counts_quadlets: bool,
+}
+
+impl Counter {
+    /// Largest count a small ("-X##") counter can carry
+    pub const MAX_SMALL_COUNT: u32 = 64 * 64 - 1;
+
+    /// Counter constructor
+    pub fn new_with_code_and_count(code: &str, count: u32) -> ParsideResult<Self> {
+        let (hs, ss) = Self::sizage(code.as_bytes())?;
+        if code.len() != hs {
+            return Err(ParsideError::Unexpected(format!("Unknown counter code {code:?}")));
+        }
+        if u64::from(count) >= 1 << (6 * ss) {
+            return Err(ParsideError::Unexpected(format!(
+                "Count {count} does not fit counter code {code:?}"
+            )));
+        }
+        Ok(Self { code: code.to_string(), count, counts_quadlets: false })
+    }
+
+    /// Counter with small `code` of the `version` table, switching to its big variant when
+    /// `count` does not fit. Fails when the table has no big variant of the code.
+    pub fn new_with_small_code_and_count(
+        code: &str,
+        count: u32,
+        version: &GenusVersion,
+    ) -> ParsideResult<Self> {
+        if count <= Self::MAX_SMALL_COUNT {
+            return Self::new_with_code_and_count(code, count);
+        }
+        match version.big_code(code) {
+            Some(big_code) => Self::new_with_code_and_count(&big_code, count),
+            None => Err(ParsideError::Unexpected(format!(
+                "Count {count} does not fit counter code {code:?} of {version} table"
+            ))),
+        }
+    }
+
+    /// Parse counter from the beginning of qb64 bytes
+    pub fn new_with_qb64b(qb64b: &[u8]) -> ParsideResult<Self> {
+        // hard code is told by its first two characters, any counter takes at least a quadlet
+        if qb64b.len() < 2 {
+            return Err(ParsideError::Incomplete(4 - qb64b.len()));
+        }
+        let (hs, ss) = Self::sizage(qb64b)?;
+        if qb64b.len() < hs + ss {
+            return Err(ParsideError::Incomplete(hs + ss - qb64b.len()));
+        }
+
+        let mut count = 0u32;
+        for char in &qb64b[hs..hs + ss] {
+            count = (count << 6) | Self::b64_index(*char)?;
+        }
+        let code = String::from_utf8(qb64b[..hs].to_vec())
+            .map_err(|err| ParsideError::Common(err.to_string()))?;
+        Ok(Self { code, count, counts_quadlets: false })
+    }
+
+    /// Parse counter from the beginning of qb2 bytes
+    pub fn new_with_qb2(qb2: &[u8]) -> ParsideResult<Self> {
+        if qb2.len() < 2 {
+            return Err(ParsideError::Incomplete(3 - qb2.len()));
+        }
+        // first two sextets select the hard code and with it the counter size
+        let first = qb2[0] >> 2;
+        let second = ((qb2[0] & 0x03) << 4) | (qb2[1] >> 4);
+        let size = match (first, second) {
+            (62, 52) | (62, 62) | (62, 63) => 6,
+            _ => 3,
+        };
+        if qb2.len() < size {
+            return Err(ParsideError::Incomplete(size - qb2.len()));
+        }
+        Self::new_with_qb64b(&Self::qb2_to_qb64b(&qb2[..size]))
+    }
+
+    /// Code of the counter
+    pub fn code(&self) -> String {
+        self.code.clone()
+    }
+
+    /// Small variant of the counter code, same as `code` for small counters
+    pub fn small_code(&self) -> String {
+        match self.code.strip_prefix("-0") {
+            Some(selector) if self.code.len() == 3 => format!("-{selector}"),
+            _ => self.code.clone(),
+        }
+    }
+
+    /// Count of group items or material quadlets
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Whether the count is in quadlets the group items fill rather than in items
+    pub(crate) fn counts_quadlets(&self) -> bool {
+        self.counts_quadlets
+    }
+
+    /// Same counter, counting quadlets rather than group items
+    pub(crate) fn counting_quadlets(self) -> Self {
+        Self { counts_quadlets: true, ..self }
+    }
+
+    /// Get qb64 representation of the counter
+    pub fn qb64(&self) -> ParsideResult<String> {
+        let qb64b = self.qb64b()?;
+        String::from_utf8(qb64b).map_err(|err| ParsideError::Common(err.to_string()))
+    }
+
+    /// Get qb64b representation of the counter
+    pub fn qb64b(&self) -> ParsideResult<Vec<u8>> {
+        let (_, ss) = Self::sizage(self.code.as_bytes())?;
+        let mut out = self.code.as_bytes().to_vec();
+        for shift in (0..ss).rev() {
+            out.push(B64_CHARS[((self.count >> (6 * shift)) & 0x3f) as usize]);
+        }
+        Ok(out)
+    }
+
+    /// Get qb2 representation of the counter
+    pub fn qb2(&self) -> ParsideResult<Vec<u8>> {
+        Self::qb64b_to_qb2(&self.qb64b()?)
+    }
+
+    /// Get size of qb64 representation of the counter
+    pub fn full_size(&self) -> ParsideResult<usize> {
+        let (hs, ss) = Self::sizage(self.code.as_bytes())?;
+        Ok(hs + ss)
+    }
+
+    /// Hard and soft sizes of the counter code starting `code`
+    fn sizage(code: &[u8]) -> ParsideResult<(usize, usize)> {
+        match code {
+            [b'-', b'-' | b'_', ..] => Ok((5, 3)),
+            [b'-', b'0', selector, ..] if selector.is_ascii_alphabetic() => Ok((3, 5)),
+            [b'-', b'0'] => Ok((3, 5)),
+            [b'-', selector, ..] if selector.is_ascii_alphabetic() => Ok((2, 2)),
+            _ => Err(ParsideError::Unexpected(format!(
+                "Unknown counter code {:?}",
+                String::from_utf8_lossy(&code[..code.len().min(3)])
+            ))),
+        }
+    }
+
+    fn b64_index(char: u8) -> ParsideResult<u32> {
+        B64_CHARS.iter().position(|b64| *b64 == char).map(|index| index as u32).ok_or_else(|| {
+            ParsideError::Unexpected(format!("Unexpected byte {char:#04x} in Base64 material"))
+        })
+    }
+
+    /// Convert whole quadlets of qualified Base64 material into qualified base2
+    pub(crate) fn qb64b_to_qb2(qb64b: &[u8]) -> ParsideResult<Vec<u8>> {
+        let mut out = Vec::with_capacity(qb64b.len() / 4 * 3);
+        for quadlet in qb64b.chunks(4) {
+            let mut triplet = 0u32;
+            for char in quadlet {
+                triplet = (triplet << 6) | Self::b64_index(*char)?;
+            }
+            out.extend_from_slice(&triplet.to_be_bytes()[1..]);
+        }
+        Ok(out)
+    }
+
+    /// Convert whole triplets of qualified base2 material into qualified Base64
+    pub(crate) fn qb2_to_qb64b(qb2: &[u8]) -> Vec<u8> {
+        let mut out = Vec::with_capacity(qb2.len() / 3 * 4);
+        for triplet in qb2.chunks(3) {
+            let triplet = u32::from_be_bytes([0, triplet[0], triplet[1], triplet[2]]);
+            for shift in (0..4).rev() {
+                out.push(B64_CHARS[((triplet >> (6 * shift)) & 0x3f) as usize]);
+            }
+        }
+        out
+    }
+}
+
+impl From<cesride::Counter> for Counter {
+    fn from(counter: cesride::Counter) -> Self {
+        Self { code: counter.code(), count: counter.count(), counts_quadlets: false }
+    }
+}
+
+impl TryFrom<Counter> for cesride::Counter {
+    type Error = ParsideError;
+
+    /// Fails for codes missing from the cesride table, e.g. of the CESR 2.0 table
+    fn try_from(counter: Counter) -> ParsideResult<Self> {
+        Ok(cesride::Counter::new_with_code_and_count(&counter.code, counter.count)?)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_counter_round_trip() {
+        let counter = Counter::new_with_qb64b(b"-AAB").unwrap();
+        assert_eq!(Codex::ControllerIdxSigs, counter.code());
+        assert_eq!(1, counter.count());
+        assert_eq!(4, counter.full_size().unwrap());
+        assert_eq!(counter, Counter::new_with_qb2(&counter.qb2().unwrap()).unwrap());
+
+        let counter = Counter::new_with_qb64b(b"-0VAAAQA").unwrap();
+        assert_eq!(Codex::BigAttachedMaterialQuadlets, counter.code());
+        assert_eq!(Codex::AttachedMaterialQuadlets, counter.small_code());
+        assert_eq!(1024, counter.count());
+        assert_eq!("-0VAAAQA", counter.qb64().unwrap());
+        assert_eq!(counter, Counter::new_with_qb2(&counter.qb2().unwrap()).unwrap());
+    }
+
+    #[test]
+    pub fn test_counter_switches_to_big_code() {
+        let v1 = GenusVersion::default();
+        let counter =
+            Counter::new_with_small_code_and_count(Codex::ControllerIdxSigs, 4095, &v1).unwrap();
+        assert_eq!("-A__", counter.qb64().unwrap());
+
+        // the KERI 1.0 table has a big variant of attached material quadlets only
+        let counter =
+            Counter::new_with_small_code_and_count(Codex::AttachedMaterialQuadlets, 4096, &v1)
+                .unwrap();
+        assert_eq!(Codex::BigAttachedMaterialQuadlets, counter.code());
+        assert_eq!("-0VAABAA", counter.qb64().unwrap());
+        assert!(
+            Counter::new_with_small_code_and_count(Codex::ControllerIdxSigs, 4096, &v1).is_err()
+        );
+        assert!(Counter::new_with_code_and_count(Codex::ControllerIdxSigs, 4096).is_err());
+
+        let v2 = GenusVersion::new(GenusVersion::KERI_ACDC, 2, 0);
+        let counter =
+            Counter::new_with_small_code_and_count(CodexV2::ControllerIdxSigs, 4096, &v2).unwrap();
+        assert_eq!("-0KAABAA", counter.qb64().unwrap());
+    }
+
+    #[test]
+    pub fn test_counter_from_cesride_counter() {
+        let counter =
+            cesride::Counter::new_with_code_and_count(Codex::ControllerIdxSigs, 2).unwrap();
+        let converted = Counter::from(counter.clone());
+        assert_eq!(counter.qb64().unwrap(), converted.qb64().unwrap());
+        assert_eq!(counter, cesride::Counter::try_from(converted).unwrap());
+
+        let counter = Counter::new_with_code_and_count(CodexV2::ESSRPayloadGroup, 1).unwrap();
+        assert!(cesride::Counter::try_from(counter).is_err());
+    }
+
+    #[test]
+    pub fn test_counter_from_short_or_malformed_bytes() {
+        assert_eq!(Err(ParsideError::Incomplete(3)), Counter::new_with_qb64b(b"-"));
+        assert_eq!(Err(ParsideError::Incomplete(6)), Counter::new_with_qb64b(b"-0"));
+        assert_eq!(Err(ParsideError::Incomplete(5)), Counter::new_with_qb64b(b"-0V"));
+        assert_eq!(Err(ParsideError::Incomplete(3)), Counter::new_with_qb2(&[0xfb, 0x40, 0x00]));
+        assert!(Counter::new_with_qb64b(b"-A\xdf\xa5").is_err());
+        assert!(Counter::new_with_qb64b(b"-?AB").is_err());
+    }
+}
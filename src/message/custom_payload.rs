@@ -1,18 +1,107 @@
 use rmp_serde as serde_mgpk;
 use serde::de::DeserializeOwned;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
-use std::io::Cursor;
+use std::fmt;
+use std::io::{Cursor, ErrorKind};
 
 use crate::error::{ParsideError, ParsideResult};
+use cesride::{Creder, Serder};
 
-/// Datastructures representing custom payload
-#[derive(Debug)]
+/// Version string of KERI/ACDC message, e.g. `KERI10JSON00011c_`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VersionString {
+    pub protocol: String,
+    pub major: u8,
+    pub minor: u8,
+    pub kind: String,
+    pub size: usize,
+}
+
+impl VersionString {
+    /// Size of version string
+    pub const SIZE: usize = 17;
+    /// Maximum offset of version string from the beginning of serialized message
+    pub const MAX_OFFSET: usize = 12;
+
+    /// Find version string near the beginning of serialized message
+    pub fn from_raw(raw: &[u8]) -> Option<VersionString> {
+        let end = raw.len().min(Self::MAX_OFFSET + Self::SIZE);
+        raw[..end].windows(Self::SIZE).find_map(Self::parse)
+    }
+
+    /// Parse `PPPPvvKKKKssssss_` version string
+    fn parse(vs: &[u8]) -> Option<VersionString> {
+        let is_hex = |b: &u8| b.is_ascii_digit() || (b'a'..=b'f').contains(b);
+        if vs.len() != Self::SIZE
+            || !vs[..4].iter().all(u8::is_ascii_uppercase)
+            || !vs[4..6].iter().all(is_hex)
+            || !vs[6..10].iter().all(u8::is_ascii_uppercase)
+            || !vs[10..16].iter().all(is_hex)
+            || vs[16] != b'_'
+        {
+            return None;
+        }
+
+        // all bytes are checked to be ASCII above
+        let text = |range: std::ops::Range<usize>| String::from_utf8_lossy(&vs[range]).to_string();
+        Some(VersionString {
+            protocol: text(0..4),
+            major: u8::from_str_radix(&text(4..5), 16).ok()?,
+            minor: u8::from_str_radix(&text(5..6), 16).ok()?,
+            kind: text(6..10),
+            size: usize::from_str_radix(&text(10..16), 16).ok()?,
+        })
+    }
+}
+
+impl fmt::Display for VersionString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{:x}{:x}{}{:06x}_",
+            self.protocol, self.major, self.minor, self.kind, self.size
+        )
+    }
+}
+
+/// How the end of custom payload is found in the stream
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Framing {
+    /// Payload ends where its deserializer stops
+    #[default]
+    Deserializer,
+    /// Payload spans the size declared by its version string, as keripy and keriox frame messages
+    VersionString,
+}
+
+/// Datastructures representing custom payload. Serializes as the deserialized value together
+/// with the version string, leaving out the raw bytes.
+#[derive(Debug, Serialize)]
 pub struct CustomPayload {
     pub value: JsonValue,
+    /// Exact bytes the payload was parsed from
+    #[serde(skip)]
+    pub raw: Vec<u8>,
+    /// Version string of KERI/ACDC message, `None` for other payloads
+    pub version: Option<VersionString>,
 }
 
 impl CustomPayload {
+    fn new(value: JsonValue, raw: &[u8]) -> Self {
+        Self { value, raw: raw.to_vec(), version: VersionString::from_raw(raw) }
+    }
+
+    /// Build KERI event from the raw payload bytes
+    pub fn serder(&self) -> ParsideResult<Serder> {
+        Serder::new_with_raw(&self.raw).map_err(ParsideError::from)
+    }
+
+    /// Build ACDC credential from the raw payload bytes
+    pub fn creder(&self) -> ParsideResult<Creder> {
+        Creder::new_with_raw(&self.raw).map_err(ParsideError::from)
+    }
+
     /// Convert custom payload to specific type
     pub fn to_typed_message<D>(&self) -> ParsideResult<D>
     where
@@ -23,31 +112,227 @@ impl CustomPayload {
     }
 
     /// Parse custom payload from JSON representation
-    pub(crate) fn from_json_stream(s: &[u8]) -> ParsideResult<(&[u8], CustomPayload)> {
+    pub(crate) fn from_json_stream(
+        s: &[u8],
+        framing: Framing,
+    ) -> ParsideResult<(&[u8], CustomPayload)> {
+        Self::from_stream(s, framing, "JSON", Self::json_value)
+    }
+
+    /// Parse custom payload from CBOR representation
+    pub(crate) fn from_cbor_stream(
+        s: &[u8],
+        framing: Framing,
+    ) -> ParsideResult<(&[u8], CustomPayload)> {
+        Self::from_stream(s, framing, "CBOR", Self::cbor_value)
+    }
+
+    /// Parse custom payload from MessagePack representation
+    pub(crate) fn from_mgpk_stream(
+        s: &[u8],
+        framing: Framing,
+    ) -> ParsideResult<(&[u8], CustomPayload)> {
+        Self::from_stream(s, framing, "MGPK", Self::mgpk_value)
+    }
+
+    fn from_stream<'a>(
+        s: &'a [u8],
+        framing: Framing,
+        kind: &str,
+        parse: fn(&[u8]) -> ParsideResult<(JsonValue, usize)>,
+    ) -> ParsideResult<(&'a [u8], CustomPayload)> {
+        let (value, size) = match framing {
+            Framing::Deserializer => match parse(s) {
+                // wait for the size the version string declares rather than a byte at a time
+                Err(ParsideError::Incomplete(needed)) => {
+                    let declared = VersionString::from_raw(s).map_or(0, |version| version.size);
+                    return Err(ParsideError::Incomplete(
+                        needed.max(declared.saturating_sub(s.len())),
+                    ));
+                }
+                result => result?,
+            },
+            Framing::VersionString => Self::framed_value(s, kind, parse)?,
+        };
+        let (raw, rest) = s.split_at(size);
+        Ok((rest, CustomPayload::new(value, raw)))
+    }
+
+    /// Value of the message framed by the size its version string declares, returned with the
+    /// declared size once checked against the serialized value
+    fn framed_value(
+        s: &[u8],
+        kind: &str,
+        parse: fn(&[u8]) -> ParsideResult<(JsonValue, usize)>,
+    ) -> ParsideResult<(JsonValue, usize)> {
+        let sniff_size = VersionString::MAX_OFFSET + VersionString::SIZE;
+        let version = match VersionString::from_raw(s) {
+            Some(version) => version,
+            None if s.len() < sniff_size => {
+                return Err(ParsideError::Incomplete(sniff_size - s.len()))
+            }
+            None => {
+                return Err(ParsideError::PayloadDeserializeError(
+                    "Missing version string".to_string(),
+                ))
+            }
+        };
+        if version.kind != kind {
+            return Err(ParsideError::PayloadDeserializeError(format!(
+                "Version string declares {} serialization of {} payload",
+                version.kind, kind
+            )));
+        }
+        if s.len() < version.size {
+            return Err(ParsideError::Incomplete(version.size - s.len()));
+        }
+
+        // only JSON may pad the serialized value with whitespace
+        let frame = &s[..version.size];
+        match parse(frame) {
+            Ok((value, end))
+                if frame[end..].iter().all(|b| kind == "JSON" && b.is_ascii_whitespace()) =>
+            {
+                Ok((value, version.size))
+            }
+            Ok((_, end)) => {
+                Err(ParsideError::VersionSizeMismatch { declared: version.size, actual: end })
+            }
+            Err(ParsideError::Incomplete(_)) => match parse(s) {
+                Ok((_, end)) => {
+                    Err(ParsideError::VersionSizeMismatch { declared: version.size, actual: end })
+                }
+                // the message runs past the buffered bytes, so it spans at least one more
+                Err(ParsideError::Incomplete(_)) => Err(ParsideError::VersionSizeMismatch {
+                    declared: version.size,
+                    actual: s.len() + 1,
+                }),
+                Err(err) => Err(err),
+            },
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Deserialize JSON value from the beginning of bytes, returning it with its size
+    fn json_value(s: &[u8]) -> ParsideResult<(JsonValue, usize)> {
         let mut stream = serde_json::Deserializer::from_slice(s).into_iter::<JsonValue>();
         match stream.next() {
-            Some(Ok(value)) => Ok((&s[stream.byte_offset()..], CustomPayload { value })),
+            Some(Ok(value)) => Ok((value, stream.byte_offset())),
+            Some(Err(err)) if err.is_eof() => Err(ParsideError::Incomplete(1)),
             Some(Err(err)) => Err(ParsideError::PayloadDeserializeError(err.to_string())),
             None => Err(ParsideError::PayloadDeserializeError("End of stream".to_string())),
         }
     }
 
-    /// Parse custom payload from CBOR representation
-    pub(crate) fn from_cbor_stream(s: &[u8]) -> ParsideResult<(&[u8], CustomPayload)> {
+    /// Deserialize CBOR value from the beginning of bytes, returning it with its size
+    fn cbor_value(s: &[u8]) -> ParsideResult<(JsonValue, usize)> {
         let mut stream = serde_cbor::Deserializer::from_slice(s).into_iter::<JsonValue>();
         match stream.next() {
-            Some(Ok(value)) => Ok((&s[stream.byte_offset()..], CustomPayload { value })),
+            Some(Ok(value)) => Ok((value, stream.byte_offset())),
+            Some(Err(err)) if err.is_eof() => Err(ParsideError::Incomplete(1)),
             Some(Err(err)) => Err(ParsideError::PayloadDeserializeError(err.to_string())),
             None => Err(ParsideError::PayloadDeserializeError("End of stream".to_string())),
         }
     }
 
-    /// Parse custom payload from MessagePack representation
-    pub(crate) fn from_mgpk_stream(s: &[u8]) -> ParsideResult<(&[u8], CustomPayload)> {
+    /// Deserialize MessagePack value from the beginning of bytes, returning it with its size
+    fn mgpk_value(s: &[u8]) -> ParsideResult<(JsonValue, usize)> {
         let mut deser = serde_mgpk::Deserializer::new(Cursor::new(s));
         match Deserialize::deserialize(&mut deser) {
-            Ok(value) => Ok((&s[deser.get_ref().position() as usize..], CustomPayload { value })),
+            Ok(value) => Ok((value, deser.get_ref().position() as usize)),
+            Err(serde_mgpk::decode::Error::InvalidMarkerRead(err))
+            | Err(serde_mgpk::decode::Error::InvalidDataRead(err))
+                if err.kind() == ErrorKind::UnexpectedEof =>
+            {
+                Err(ParsideError::Incomplete(1))
+            }
             Err(err) => Err(ParsideError::PayloadDeserializeError(err.to_string())),
         }
     }
 }
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use cesride::Sadder;
+
+    const KERI_EVENT: &str = r#"{"v":"KERI10JSON00015a_","t":"icp","d":"EBAjyPZ8Ed4XXl5cVZhqAy7SuaGivQp0WqQKVXvg7oqd","i":"BEy_EvE8OUMqj0AgCJ3wOCOrIVHVtwubYAysPyaAv9VI","s":"0","kt":"1","k":["BEy_EvE8OUMqj0AgCJ3wOCOrIVHVtwubYAysPyaAv9VI"],"nt":"0","n":[],"bt":"2","b":["BC9Df6ssUZQFQZJYVUyfudw4WTQsugGcvVD_Z4ChFGE4","BEejlxZytU7gjUwtgkmNKmBWiFPKSsXjk_uxzoun8dtK"],"c":[],"a":[]}"#;
+
+    #[test]
+    pub fn test_parse_keri_event() {
+        let stream = format!("{}-AAB", KERI_EVENT);
+        let (rest, payload) =
+            CustomPayload::from_json_stream(stream.as_bytes(), Framing::Deserializer).unwrap();
+        assert_eq!(b"-AAB", rest);
+        assert_eq!(KERI_EVENT.as_bytes(), payload.raw);
+
+        let version = payload.version.as_ref().unwrap();
+        assert_eq!("KERI", version.protocol);
+        assert_eq!((1, 0), (version.major, version.minor));
+        assert_eq!("JSON", version.kind);
+        assert_eq!(KERI_EVENT.len(), version.size);
+        assert_eq!("KERI10JSON00015a_", version.to_string());
+
+        let serder = payload.serder().unwrap();
+        assert_eq!("EBAjyPZ8Ed4XXl5cVZhqAy7SuaGivQp0WqQKVXvg7oqd", serder.said().unwrap());
+        assert_eq!(KERI_EVENT.as_bytes(), serder.raw());
+        assert!(payload.creder().is_err());
+    }
+
+    #[test]
+    pub fn test_parse_payload_without_version_string() {
+        let (_, payload) =
+            CustomPayload::from_json_stream(br#"{"v":"1","t":"foo"}"#, Framing::Deserializer)
+                .unwrap();
+        assert!(payload.version.is_none());
+        assert!(payload.serder().is_err());
+    }
+
+    #[test]
+    pub fn test_frame_keri_event_by_version_string() {
+        let stream = format!("{}-AAB", KERI_EVENT);
+        let (rest, payload) =
+            CustomPayload::from_json_stream(stream.as_bytes(), Framing::VersionString).unwrap();
+        assert_eq!(b"-AAB", rest);
+        assert_eq!(KERI_EVENT.as_bytes(), payload.raw);
+
+        let padded = KERI_EVENT.replacen("00015a_", "00015c_", 1) + "\r\n-AAB";
+        let (rest, payload) =
+            CustomPayload::from_json_stream(padded.as_bytes(), Framing::VersionString).unwrap();
+        assert_eq!(b"-AAB", rest);
+        assert_eq!(KERI_EVENT.len() + 2, payload.raw.len());
+
+        assert_eq!(
+            Err(ParsideError::Incomplete(10)),
+            CustomPayload::from_json_stream(&KERI_EVENT.as_bytes()[..336], Framing::VersionString)
+                .map(|(_, payload)| payload.raw)
+        );
+    }
+
+    #[test]
+    pub fn test_frame_keri_event_with_wrong_declared_size() {
+        for declared in ["000150", "000160"] {
+            let stream = KERI_EVENT.replacen("00015a", declared, 1) + "-AAB-AAB";
+            let err = CustomPayload::from_json_stream(stream.as_bytes(), Framing::VersionString)
+                .unwrap_err();
+            assert_eq!(
+                ParsideError::VersionSizeMismatch {
+                    declared: usize::from_str_radix(declared, 16).unwrap(),
+                    actual: KERI_EVENT.len()
+                },
+                err
+            );
+        }
+
+        let stream = r#"{"v":"1","t":"foo","x":"0123456789abcdef"}"#;
+        assert!(CustomPayload::from_json_stream(stream.as_bytes(), Framing::VersionString).is_err());
+
+        // message running past the end of the buffered bytes
+        let stream = &KERI_EVENT.replacen("00015a", "000150", 1)[..340];
+        assert_eq!(
+            Err(ParsideError::VersionSizeMismatch { declared: 0x150, actual: 341 }),
+            CustomPayload::from_json_stream(stream.as_bytes(), Framing::VersionString)
+                .map(|(_, payload)| payload.raw)
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use crate::error::ParsideResult;
+use crate::MessageList;
+
+/// Domain CESR attachments are serialized in, message bodies keep the serialization they were
+/// read in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Domain {
+    /// Qualified Base64 text (qb64)
+    #[default]
+    Text,
+    /// Qualified base2 binary (qb2)
+    Binary,
+}
+
+/// Convert a stream of messages to `domain`. Custom payloads are copied untouched, attachments
+/// and native bodies of either domain are re-encoded, counters of framing groups such as `-V`
+/// counting quadlets in text and triplets in binary domain. Unlike [`MessageList`] parsing,
+/// bytes that are not a message fail the conversion rather than being left over.
+pub fn transcode(input: &[u8], domain: Domain) -> ParsideResult<Vec<u8>> {
+    let message_list = MessageList::from_complete_stream_bytes(input)?;
+    let mut out = Vec::with_capacity(input.len());
+    message_list.write_to(&mut out, domain)?;
+    Ok(out)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::error::ParsideError;
+    use crate::message::counter::Counter;
+    use crate::message::groups::tests::{
+        ATTACHED_MATERIAL_QUADLETS, KERI_EVENT, TRANS_IDX_SIG_GROUPS,
+    };
+
+    #[test]
+    pub fn test_transcode_round_trip() {
+        let text =
+            [KERI_EVENT, ATTACHED_MATERIAL_QUADLETS, KERI_EVENT, TRANS_IDX_SIG_GROUPS].concat();
+        let binary = transcode(&text, Domain::Binary).unwrap();
+        assert!(binary.starts_with(KERI_EVENT));
+        assert_eq!(text.len() - binary.len(), (text.len() - 2 * KERI_EVENT.len()) / 4);
+
+        // quadlet count of the -V group is carried over as count of triplets
+        let counter = Counter::new_with_qb2(&binary[KERI_EVENT.len()..]).unwrap();
+        assert_eq!((ATTACHED_MATERIAL_QUADLETS.len() - 4) / 4, counter.count() as usize);
+
+        assert_eq!(text, transcode(&binary, Domain::Text).unwrap());
+        assert_eq!(binary, transcode(&binary, Domain::Binary).unwrap());
+    }
+
+    #[test]
+    pub fn test_transcode_malformed_stream() {
+        let text = [KERI_EVENT, b"-AABAAB"].concat();
+        let err = transcode(&text, Domain::Binary).unwrap_err();
+        assert!(matches!(err, ParsideError::Incomplete(..)));
+
+        let text = [KERI_EVENT, b"-CAB####"].concat();
+        match transcode(&text, Domain::Binary).unwrap_err() {
+            ParsideError::Parse { offset, .. } => assert!(offset >= KERI_EVENT.len()),
+            err => panic!("Unexpected error {err:?}"),
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use std::iter::Peekable;
+use std::slice::Iter;
+
+use crate::error::{ParsideError, ParsideResult};
+use crate::message::custom_payload::CustomPayload;
+use crate::message::groups::CesrGroup;
+use crate::message::message::Message;
+use crate::message::native_body::NativeBody;
+
+/// Message body together with the CESR groups attached to it
+#[derive(Debug, Clone)]
+pub struct Event<'a> {
+    pub body: EventBody<'a>,
+    /// Attachment groups in stream order, pipelined `-V` groups are kept as they are
+    pub attachments: Vec<&'a CesrGroup>,
+}
+
+impl<'a> Event<'a> {
+    /// Attachment groups with pipelined `-V` groups replaced by the groups they carry
+    pub fn groups(&self) -> Vec<&'a CesrGroup> {
+        let mut groups = vec![];
+        for attachment in &self.attachments {
+            Self::unwrap_group(attachment, &mut groups);
+        }
+        groups
+    }
+
+    fn unwrap_group(group: &'a CesrGroup, groups: &mut Vec<&'a CesrGroup>) {
+        match group {
+            CesrGroup::AttachedMaterialQuadletsVariant { value } => {
+                for group in &value.value {
+                    Self::unwrap_group(group, groups);
+                }
+            }
+            _ => groups.push(group),
+        }
+    }
+}
+
+/// Message body of an event, custom payload or CESR 2.0 native body
+#[derive(Debug, Clone, Copy)]
+pub enum EventBody<'a> {
+    Custom { value: &'a CustomPayload },
+    Native { value: &'a NativeBody },
+}
+
+impl<'a> EventBody<'a> {
+    /// Get custom payload of the event
+    pub fn payload(&self) -> ParsideResult<&'a CustomPayload> {
+        match self {
+            EventBody::Custom { value } => Ok(value),
+            EventBody::Native { .. } => Err(ParsideError::NotExist),
+        }
+    }
+
+    /// Get native body of the event
+    pub fn native_body(&self) -> ParsideResult<&'a NativeBody> {
+        match self {
+            EventBody::Native { value } => Ok(value),
+            EventBody::Custom { .. } => Err(ParsideError::NotExist),
+        }
+    }
+}
+
+/// Iterator pairing parsed message bodies with the groups following them
+#[derive(Debug)]
+pub struct Events<'a> {
+    messages: Peekable<Iter<'a, Message>>,
+}
+
+impl<'a> Events<'a> {
+    pub(crate) fn new(messages: &'a [Message]) -> Self {
+        Self { messages: messages.iter().peekable() }
+    }
+}
+
+impl<'a> Iterator for Events<'a> {
+    type Item = ParsideResult<Event<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let body = match self.messages.next()? {
+            Message::Custom { value, .. } => EventBody::Custom { value },
+            Message::Native { value, .. } => EventBody::Native { value },
+            Message::Group { .. } => {
+                // skip the rest of the orphaned attachments so iteration resumes at the next body
+                while let Some(Message::Group { .. }) = self.messages.peek() {
+                    self.messages.next();
+                }
+                return Some(Err(ParsideError::Unexpected(
+                    "Attachments without preceding message body".to_string(),
+                )));
+            }
+        };
+
+        let mut attachments = vec![];
+        while let Some(Message::Group { value, .. }) = self.messages.peek() {
+            attachments.push(value);
+            self.messages.next();
+        }
+        Some(Ok(Event { body, attachments }))
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::MessageList;
+
+    use super::*;
+
+    use crate::message::groups::tests::{
+        ATTACHED_MATERIAL_QUADLETS, CONTROLLER_IDX_SIGS, NON_TRANS_RECEIPT_COUPLES, PAYLOAD,
+    };
+
+    const PAYLOAD_2: &[u8] = br#"{"v":"2","t":"bla"}"#;
+
+    #[test]
+    pub fn test_pair_bodies_with_attachments() {
+        let stream =
+            [PAYLOAD, CONTROLLER_IDX_SIGS, NON_TRANS_RECEIPT_COUPLES, PAYLOAD_2, PAYLOAD].concat();
+        let (_, message_list) = MessageList::from_stream_bytes(&stream).unwrap();
+        let events = message_list.events().collect::<ParsideResult<Vec<_>>>().unwrap();
+
+        assert_eq!(3, events.len());
+        assert_eq!(PAYLOAD, events[0].body.payload().unwrap().raw);
+        assert_eq!(2, events[0].attachments.len());
+        assert!(matches!(events[0].attachments[0], CesrGroup::ControllerIdxSigsVariant { .. }));
+        assert!(matches!(
+            events[0].attachments[1],
+            CesrGroup::NonTransReceiptCouplesVariant { .. }
+        ));
+        assert_eq!(PAYLOAD_2, events[1].body.payload().unwrap().raw);
+        assert!(events[1].attachments.is_empty());
+        assert!(events[2].attachments.is_empty());
+    }
+
+    #[test]
+    pub fn test_pair_body_with_pipelined_attachments() {
+        let stream = [PAYLOAD, ATTACHED_MATERIAL_QUADLETS, CONTROLLER_IDX_SIGS].concat();
+        let (_, message_list) = MessageList::from_stream_bytes(&stream).unwrap();
+        let event = message_list.events().next().unwrap().unwrap();
+
+        assert_eq!(2, event.attachments.len());
+        let groups = event.groups();
+        assert_eq!(4, groups.len());
+        assert!(matches!(groups[0], CesrGroup::ControllerIdxSigsVariant { .. }));
+        assert!(matches!(groups[1], CesrGroup::WitnessIdxSigsVariant { .. }));
+        assert!(matches!(groups[2], CesrGroup::FirstSeenReplayCouplesVariant { .. }));
+        assert!(matches!(groups[3], CesrGroup::ControllerIdxSigsVariant { .. }));
+    }
+
+    #[test]
+    pub fn test_pair_native_body_with_attachments() {
+        let native = "-GAO0J_tXixn0J_dEADZ055vgh5utgSY3OOL1lW0m1pJ1W0Ia6-SVuGa0OqE";
+        let stream =
+            [b"-_AAACAA", native.as_bytes(), b"-KAW", &CONTROLLER_IDX_SIGS[4..], PAYLOAD].concat();
+        let (_, message_list) = MessageList::from_stream_bytes(&stream).unwrap();
+        let events = message_list.events().collect::<ParsideResult<Vec<_>>>().unwrap();
+
+        assert_eq!(2, events.len());
+        let body = events[0].body.native_body().unwrap();
+        assert_eq!(Some("ixn".to_string()), body.ilk());
+        assert!(events[0].body.payload().is_err());
+        assert_eq!(1, events[0].attachments.len());
+        assert!(matches!(events[0].attachments[0], CesrGroup::ControllerIdxSigsVariant { .. }));
+        assert_eq!(PAYLOAD, events[1].body.payload().unwrap().raw);
+    }
+
+    #[test]
+    pub fn test_attachments_without_body() {
+        let stream = [CONTROLLER_IDX_SIGS, CONTROLLER_IDX_SIGS, PAYLOAD].concat();
+        let (_, message_list) = MessageList::from_stream_bytes(&stream).unwrap();
+        let mut events = message_list.events();
+
+        assert!(events.next().unwrap().is_err());
+        assert_eq!(PAYLOAD, events.next().unwrap().unwrap().body.payload().unwrap().raw);
+        assert!(events.next().is_none());
+    }
+}
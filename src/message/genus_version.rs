@@ -0,0 +1,142 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::error::{ParsideError, ParsideResult};
+use crate::message::counter::{Codex, CodexV2, Counter};
+
+/// Groups of the CESR 2.0 table with their KERI 1.0 counterparts
+const V2_GROUP_CODES: [(&str, &str); 12] = [
+    (CodexV2::ControllerIdxSigs, Codex::ControllerIdxSigs),
+    (CodexV2::WitnessIdxSigs, Codex::WitnessIdxSigs),
+    (CodexV2::NonTransReceiptCouples, Codex::NonTransReceiptCouples),
+    (CodexV2::TransReceiptQuadruples, Codex::TransReceiptQuadruples),
+    (CodexV2::FirstSeenReplayCouples, Codex::FirstSeenReplayCouples),
+    (CodexV2::TransIdxSigGroups, Codex::TransIdxSigGroups),
+    (CodexV2::TransLastIdxSigGroups, Codex::TransLastIdxSigGroups),
+    (CodexV2::SealSourceCouples, Codex::SealSourceCouples),
+    (CodexV2::SealSourceTriples, Codex::SealSourceTriples),
+    (CodexV2::PathedMaterialCouples, Codex::PathedMaterialQuadlets),
+    (CodexV2::AttachmentGroup, Codex::AttachedMaterialQuadlets),
+    (CodexV2::ESSRPayloadGroup, Codex::ESSRPayloadGroup),
+];
+
+/// Groups of the KERI 1.0 table with their big counter codes
+const V1_BIG_CODES: [(&str, &str); 3] = [
+    (Codex::PathedMaterialQuadlets, Codex::BigPathedMaterialQuadlets),
+    (Codex::AttachedMaterialQuadlets, Codex::BigAttachedMaterialQuadlets),
+    (Codex::ESSRPayloadGroup, Codex::BigESSRPayloadGroup),
+];
+
+/// Genus and version of the code tables a CESR stream is parsed with. Streams start out with
+/// KERI/ACDC 1.0 tables, a genus/version counter (`-_AAA###`, or `--AAA###` of the 1.0 table)
+/// switches tables for the rest of the stream or until the next genus/version counter.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GenusVersion {
+    pub genus: String,
+    pub major: u8,
+    pub minor: u16,
+}
+
+impl Default for GenusVersion {
+    fn default() -> Self {
+        Self::new(Self::KERI_ACDC, 1, 0)
+    }
+}
+
+impl GenusVersion {
+    /// Genus of the KERI and ACDC protocol stack
+    pub const KERI_ACDC: &'static str = "AAA";
+
+    pub fn new(genus: &str, major: u8, minor: u16) -> Self {
+        Self { genus: genus.to_string(), major, minor }
+    }
+
+    /// Genus/version counter selecting these tables
+    pub fn counter(&self) -> ParsideResult<Counter> {
+        let selector = if self.major < 2 { "--" } else { "-_" };
+        let count = (u32::from(self.major) << 12) | u32::from(self.minor);
+        Counter::new_with_code_and_count(&format!("{selector}{}", self.genus), count)
+    }
+
+    /// Genus and version selected by `counter`, `None` when it is not a genus/version counter
+    pub(crate) fn from_counter(counter: &Counter) -> ParsideResult<Option<Self>> {
+        let code = counter.code();
+        let genus = match code.strip_prefix("--").or_else(|| code.strip_prefix("-_")) {
+            Some(genus) => genus,
+            None => return Ok(None),
+        };
+        let version =
+            Self::new(genus, (counter.count() >> 12) as u8, (counter.count() & 0xfff) as u16);
+        if genus != Self::KERI_ACDC || !(1..=2).contains(&version.major) {
+            return Err(ParsideError::Unexpected(format!("Unsupported genus/version {version}")));
+        }
+        Ok(Some(version))
+    }
+
+    /// Whether group counters count quadlets rather than group items
+    pub(crate) fn counts_quadlets(&self) -> bool {
+        self.major >= 2
+    }
+
+    /// KERI 1.0 code of the group with small counter `code` in these tables, `None` for groups
+    /// without a 1.0 counterpart
+    pub(crate) fn group_code<'a>(&self, code: &'a str) -> Option<&'a str> {
+        if self.major < 2 {
+            return Some(code);
+        }
+        V2_GROUP_CODES.iter().find(|(v2, _)| *v2 == code).map(|(_, v1)| *v1)
+    }
+
+    /// Big variant of small counter `code` of these tables, `None` if the tables lack one
+    pub fn big_code(&self, code: &str) -> Option<String> {
+        match code.strip_prefix('-') {
+            Some(selector) if self.major >= 2 && selector.len() == 1 => {
+                Some(format!("-0{selector}"))
+            }
+            _ if self.major < 2 => V1_BIG_CODES
+                .iter()
+                .find(|(small, _)| *small == code)
+                .map(|(_, big)| big.to_string()),
+            _ => None,
+        }
+    }
+
+    /// Small counter code of the group with KERI 1.0 `code` in these tables, `None` for groups
+    /// missing from them
+    pub(crate) fn table_code<'a>(&self, code: &'a str) -> Option<&'a str> {
+        if self.major < 2 {
+            return Some(code);
+        }
+        V2_GROUP_CODES.iter().find(|(_, v1)| *v1 == code).map(|(v2, _)| *v2)
+    }
+}
+
+impl fmt::Display for GenusVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}.{}", self.genus, self.major, self.minor)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_genus_version_counter() {
+        let version = GenusVersion::new(GenusVersion::KERI_ACDC, 2, 0);
+        let counter = version.counter().unwrap();
+        assert_eq!("-_AAACAA", counter.qb64().unwrap());
+        assert_eq!(Some(version), GenusVersion::from_counter(&counter).unwrap());
+
+        let counter = GenusVersion::default().counter().unwrap();
+        assert_eq!("--AAABAA", counter.qb64().unwrap());
+        let counter = Counter::new_with_qb2(&counter.qb2().unwrap()).unwrap();
+        assert_eq!(Some(GenusVersion::default()), GenusVersion::from_counter(&counter).unwrap());
+
+        let counter = Counter::new_with_qb64b(b"-_AAADAA").unwrap();
+        assert!(GenusVersion::from_counter(&counter).is_err());
+        let counter = Counter::new_with_qb64b(b"-AAB").unwrap();
+        assert_eq!(None, GenusVersion::from_counter(&counter).unwrap());
+    }
+}
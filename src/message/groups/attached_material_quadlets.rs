@@ -1,12 +1,12 @@
-use crate::error::ParsideResult;
+use crate::error::{ParsideError, ParsideResult};
 use crate::message::cold_code::ColdCode;
+use crate::message::counter::{Codex, Counter};
+use crate::message::genus_version::GenusVersion;
 use crate::message::{Group, GroupItem};
-use crate::{nomify, CesrGroup};
-use cesride::counter::Codex;
-use cesride::Counter;
-use nom::multi::many0;
+use crate::CesrGroup;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AttachedMaterialQuadlets {
     pub value: Vec<CesrGroup>,
 }
@@ -26,10 +26,19 @@ impl Group<CesrGroup> for AttachedMaterialQuadlets {
 impl AttachedMaterialQuadlets {
     pub(crate) fn from_stream_bytes<'a>(
         bytes: &'a [u8],
-        _counter: &Counter,
-        _cold_code: &ColdCode,
+        counter: &Counter,
+        cold_code: &ColdCode,
+        depth: usize,
+        version: &GenusVersion,
     ) -> ParsideResult<(&'a [u8], AttachedMaterialQuadlets)> {
-        let (rest, body) = many0(nomify!(CesrGroup::from_stream_bytes))(bytes)?;
+        let size = counter.count() as usize * cold_code.quadlet_size();
+        if bytes.len() < size {
+            return Err(ParsideError::Incomplete(size - bytes.len()));
+        }
+
+        let (material, rest) = bytes.split_at(size);
+        let body = CesrGroup::from_framed_bytes(material, 0, cold_code, depth + 1, version)
+            .map_err(|err| err.shifted(rest.len()))?;
         Ok((rest, AttachedMaterialQuadlets { value: body }))
     }
 }
@@ -45,10 +54,12 @@ impl GroupItem for CesrGroup {
             CesrGroup::TransLastIdxSigGroupsVariant { value } => value.qb64(),
             CesrGroup::FirstSeenReplayCouplesVariant { value } => value.qb64(),
             CesrGroup::SealSourceCouplesVariant { value } => value.qb64(),
+            CesrGroup::SealSourceTriplesVariant { value } => value.qb64(),
             CesrGroup::AttachedMaterialQuadletsVariant { value } => value.qb64(),
             CesrGroup::SadPathSigGroupVariant { value } => value.qb64(),
             CesrGroup::SadPathSigVariant { value } => value.qb64(),
             CesrGroup::PathedMaterialQuadletsVariant { value } => value.qb64(),
+            CesrGroup::EssrPayloadsVariant { value } => value.qb64(),
         }
     }
 
@@ -62,10 +73,12 @@ impl GroupItem for CesrGroup {
             CesrGroup::TransLastIdxSigGroupsVariant { value } => value.qb64b(),
             CesrGroup::FirstSeenReplayCouplesVariant { value } => value.qb64b(),
             CesrGroup::SealSourceCouplesVariant { value } => value.qb64b(),
+            CesrGroup::SealSourceTriplesVariant { value } => value.qb64b(),
             CesrGroup::AttachedMaterialQuadletsVariant { value } => value.qb64b(),
             CesrGroup::SadPathSigGroupVariant { value } => value.qb64b(),
             CesrGroup::SadPathSigVariant { value } => value.qb64b(),
             CesrGroup::PathedMaterialQuadletsVariant { value } => value.qb64b(),
+            CesrGroup::EssrPayloadsVariant { value } => value.qb64b(),
         }
     }
 
@@ -79,10 +92,12 @@ impl GroupItem for CesrGroup {
             CesrGroup::TransLastIdxSigGroupsVariant { value } => value.qb2(),
             CesrGroup::FirstSeenReplayCouplesVariant { value } => value.qb2(),
             CesrGroup::SealSourceCouplesVariant { value } => value.qb2(),
+            CesrGroup::SealSourceTriplesVariant { value } => value.qb2(),
             CesrGroup::AttachedMaterialQuadletsVariant { value } => value.qb2(),
             CesrGroup::SadPathSigGroupVariant { value } => value.qb2(),
             CesrGroup::SadPathSigVariant { value } => value.qb2(),
             CesrGroup::PathedMaterialQuadletsVariant { value } => value.qb2(),
+            CesrGroup::EssrPayloadsVariant { value } => value.qb2(),
         }
     }
 
@@ -96,10 +111,12 @@ impl GroupItem for CesrGroup {
             Self::TransLastIdxSigGroupsVariant { value } => value.full_size(),
             Self::FirstSeenReplayCouplesVariant { value } => value.full_size(),
             Self::SealSourceCouplesVariant { value } => value.full_size(),
+            Self::SealSourceTriplesVariant { value } => value.full_size(),
             Self::AttachedMaterialQuadletsVariant { value } => value.full_size(),
             Self::SadPathSigGroupVariant { value } => value.full_size(),
             Self::SadPathSigVariant { value } => value.full_size(),
             Self::PathedMaterialQuadletsVariant { value } => value.full_size(),
+            Self::EssrPayloadsVariant { value } => value.full_size(),
         }
     }
 }
@@ -1,12 +1,13 @@
 use crate::error::{ParsideError, ParsideResult};
 use crate::message::cold_code::ColdCode;
+use crate::message::counter::{Codex as CounterCodex, Counter};
 use crate::message::parsers::Parsers;
 use crate::message::{Group, GroupItem};
-use cesride::counter::Codex as CounterCodex;
-use cesride::{Counter, Indexer, Siger};
-use nom::multi::count;
+use crate::utils::nom::counted;
+use cesride::{Indexer, Siger};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ControllerIdxSigs {
     pub value: Vec<ControllerIdxSig>,
 }
@@ -29,15 +30,15 @@ impl ControllerIdxSigs {
         counter: &Counter,
         cold_code: &ColdCode,
     ) -> ParsideResult<(&'a [u8], ControllerIdxSigs)> {
-        let (rest, body) =
-            count(Parsers::siger_parser(cold_code)?, counter.count() as usize)(bytes)?;
+        let (rest, body) = counted(Parsers::siger_parser(cold_code)?, counter, cold_code)(bytes)?;
         let body = body.into_iter().map(|siger| ControllerIdxSig { siger }).collect();
         Ok((rest, ControllerIdxSigs { value: body }))
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ControllerIdxSig {
+    #[serde(with = "crate::utils::serde::indexer")]
     pub siger: Siger,
 }
 
@@ -75,8 +76,7 @@ pub mod tests {
     pub fn test_parse_controller_idx_sigs() {
         let stream = br#"AABg3q8uNg1A2jhEAdbKGf-QupQhNnmZQx3zIyPLWBe6qqLT5ynytivf9EwJhxyhy87a0x2cezDdil4SsM2xxs0O"#;
 
-        let counter =
-            Counter::new(Some(1), None, Some(ControllerIdxSigs::CODE), None, None, None).unwrap();
+        let counter = Counter::new_with_code_and_count(ControllerIdxSigs::CODE, 1).unwrap();
         let (rest, group) =
             ControllerIdxSigs::from_stream_bytes(stream, &counter, &ColdCode::CtB64).unwrap();
 
@@ -0,0 +1,108 @@
+use crate::error::{ParsideError, ParsideResult};
+use crate::message::cold_code::ColdCode;
+use crate::message::counter::{Codex, Counter};
+use crate::message::parsers::Parsers;
+use crate::message::texter::Texter;
+use crate::message::{Group, GroupItem};
+use crate::utils::nom::counted;
+use cesride::Matter;
+use serde::{Deserialize, Serialize};
+
+/// Encrypt sender sign receiver (ESSR) payloads
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EssrPayloads {
+    pub value: Vec<EssrPayload>,
+}
+
+impl Group<EssrPayload> for EssrPayloads {
+    const CODE: &'static str = Codex::ESSRPayloadGroup;
+
+    fn new(value: Vec<EssrPayload>) -> Self {
+        Self { value }
+    }
+
+    fn value(&self) -> &Vec<EssrPayload> {
+        &self.value
+    }
+}
+
+impl EssrPayloads {
+    pub(crate) fn from_stream_bytes<'a>(
+        bytes: &'a [u8],
+        counter: &Counter,
+        cold_code: &ColdCode,
+    ) -> ParsideResult<(&'a [u8], EssrPayloads)> {
+        let (rest, body) = counted(Parsers::texter_parser(cold_code)?, counter, cold_code)(bytes)?;
+        let body = body.into_iter().map(|texter| EssrPayload { texter }).collect();
+        Ok((rest, EssrPayloads { value: body }))
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EssrPayload {
+    #[serde(with = "crate::utils::serde::matter")]
+    pub texter: Texter,
+}
+
+impl EssrPayload {
+    pub fn new(texter: Texter) -> Self {
+        Self { texter }
+    }
+}
+
+impl GroupItem for EssrPayload {
+    fn qb64(&self) -> ParsideResult<String> {
+        self.texter.qb64().map_err(ParsideError::from)
+    }
+
+    fn qb64b(&self) -> ParsideResult<Vec<u8>> {
+        self.texter.qb64b().map_err(ParsideError::from)
+    }
+
+    fn qb2(&self) -> ParsideResult<Vec<u8>> {
+        self.texter.qb2().map_err(ParsideError::from)
+    }
+
+    fn full_size(&self) -> ParsideResult<usize> {
+        let size = self.texter.full_size()?;
+        Ok(size)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::CesrGroup;
+    use cesride::matter::Codex as MatterCodex;
+
+    #[test]
+    pub fn test_parse_essr_payloads() {
+        let texter =
+            Texter::new_with_code_and_raw(MatterCodex::Bytes_L0, b"encrypted payload").unwrap();
+        let group = EssrPayloads::new(vec![EssrPayload::new(texter)]);
+        let qb64 = group.qb64().unwrap();
+        assert!(qb64.starts_with("-ZAB"));
+
+        let (rest, group) = CesrGroup::from_stream_bytes(qb64.as_bytes()).unwrap();
+        assert!(rest.is_empty());
+        match &group {
+            CesrGroup::EssrPayloadsVariant { value } => {
+                assert_eq!(1, value.value.len());
+                assert_eq!(b"encrypted payload".to_vec(), value.value[0].texter.text());
+            }
+            _ => panic!("Unexpected case"),
+        }
+
+        let qb2 = group.qb2().unwrap();
+        let (rest, group) = CesrGroup::from_stream_bytes(&qb2).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(qb64, group.qb64().unwrap());
+    }
+
+    #[test]
+    pub fn test_parse_essr_payloads_with_non_text_material() {
+        let stream = b"-ZABEFhg5my9DuMU6gw1CVk6QgkmZKBttWSXDzVzWVmxh0_K";
+        let err = CesrGroup::from_stream_bytes(stream).unwrap_err();
+        assert!(matches!(err.root_cause(), ParsideError::InvalidPrimitive { .. }));
+    }
+}
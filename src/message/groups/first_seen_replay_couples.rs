@@ -1,13 +1,14 @@
 use crate::error::ParsideResult;
 use crate::message::cold_code::ColdCode;
+use crate::message::counter::{Codex as CounterCodex, Counter};
 use crate::message::parsers::Parsers;
 use crate::message::{Group, GroupItem};
-use cesride::counter::Codex as CounterCodex;
-use cesride::{Counter, Dater, Matter, Seqner};
-use nom::multi::count;
+use crate::utils::nom::{counted, field};
+use cesride::{Dater, Matter, Seqner};
 use nom::sequence::tuple;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct FirstSeenReplayCouples {
     pub value: Vec<FirstSeenReplayCouple>,
 }
@@ -30,9 +31,13 @@ impl FirstSeenReplayCouples {
         counter: &Counter,
         cold_code: &ColdCode,
     ) -> ParsideResult<(&'a [u8], FirstSeenReplayCouples)> {
-        let (rest, body) = count(
-            tuple((Parsers::seqner_parser(cold_code)?, Parsers::dater_parser(cold_code)?)),
-            counter.count() as usize,
+        let (rest, body) = counted(
+            tuple((
+                field("firner", Parsers::seqner_parser(cold_code)?),
+                field("dater", Parsers::dater_parser(cold_code)?),
+            )),
+            counter,
+            cold_code,
         )(bytes)?;
         let body = body
             .into_iter()
@@ -43,9 +48,11 @@ impl FirstSeenReplayCouples {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct FirstSeenReplayCouple {
+    #[serde(with = "crate::utils::serde::matter")]
     pub firner: Seqner,
+    #[serde(with = "crate::utils::serde::matter")]
     pub dater: Dater,
 }
 
@@ -59,38 +66,40 @@ impl GroupItem for FirstSeenReplayCouple {
     fn qb64(&self) -> ParsideResult<String> {
         let mut out = "\0".repeat(self.full_size()?);
         let mut offset = 0;
-        let mut len = self.dater.full_size()?;
-        unsafe { out[offset..len].as_bytes_mut() }.copy_from_slice(self.dater.qb64()?.as_bytes());
+        let mut len = self.firner.full_size()?;
+        unsafe { out[offset..offset + len].as_bytes_mut() }
+            .copy_from_slice(self.firner.qb64()?.as_bytes());
         offset += len;
-        len = self.firner.full_size()?;
-        unsafe { out[offset..len].as_bytes_mut() }.copy_from_slice(self.firner.qb64()?.as_bytes());
+        len = self.dater.full_size()?;
+        unsafe { out[offset..offset + len].as_bytes_mut() }
+            .copy_from_slice(self.dater.qb64()?.as_bytes());
         Ok(out)
     }
 
     fn qb64b(&self) -> ParsideResult<Vec<u8>> {
         let mut out = vec![0u8; self.full_size()?];
         let mut offset = 0;
-        let mut len = self.dater.full_size()?;
-        out[offset..len].copy_from_slice(&self.dater.qb64b()?);
+        let mut len = self.firner.full_size()?;
+        out[offset..offset + len].copy_from_slice(&self.firner.qb64b()?);
         offset += len;
-        len = self.firner.full_size()?;
-        out[offset..len].copy_from_slice(&self.firner.qb64b()?);
+        len = self.dater.full_size()?;
+        out[offset..offset + len].copy_from_slice(&self.dater.qb64b()?);
         Ok(out)
     }
 
     fn qb2(&self) -> ParsideResult<Vec<u8>> {
         let mut out = vec![0u8; self.full_size()? / 4 * 3];
         let mut offset = 0;
-        let mut len = self.dater.full_size()? / 4 * 3;
-        out[offset..len].copy_from_slice(&self.dater.qb2()?);
+        let mut len = self.firner.full_size()? / 4 * 3;
+        out[offset..offset + len].copy_from_slice(&self.firner.qb2()?);
         offset += len;
-        len = self.firner.full_size()? / 4 * 3;
-        out[offset..len].copy_from_slice(&self.firner.qb2()?);
+        len = self.dater.full_size()? / 4 * 3;
+        out[offset..offset + len].copy_from_slice(&self.dater.qb2()?);
         Ok(out)
     }
 
     fn full_size(&self) -> ParsideResult<usize> {
-        let size = self.dater.full_size()? + self.firner.full_size()?;
+        let size = self.firner.full_size()? + self.dater.full_size()?;
         Ok(size)
     }
 }
@@ -1,5 +1,6 @@
-use crate::error::{ParsideError, ParsideResult};
-use cesride::{counter::Codex, Counter};
+use crate::error::ParsideResult;
+use crate::message::counter::{Codex, Counter};
+use crate::message::genus_version::GenusVersion;
 
 pub trait Group<T: GroupItem> {
     /// Code associated with the group
@@ -11,16 +12,17 @@ pub trait Group<T: GroupItem> {
     /// Get group values
     fn value(&self) -> &Vec<T>;
 
-    /// Get group counter
+    /// Get group counter of the KERI 1.0 table, big variant of the code is used when the count
+    /// overflows the small one and the table has one
     fn counter(&self) -> ParsideResult<Counter> {
-        Counter::new_with_code_and_count(Self::CODE, self.count()?).map_err(ParsideError::from)
+        Counter::new_with_small_code_and_count(Self::CODE, self.count()?, &GenusVersion::default())
     }
 
-    /// Get count of items in the group
+    /// Get count of items in the group, or of quadlets for groups counting material quadlets
     fn count(&self) -> ParsideResult<u32> {
         match Self::CODE {
-            Codex::AttachedMaterialQuadlets | Codex::BigAttachedMaterialQuadlets => {
-                Ok(self.full_size()? as u32 / 4 - 1)
+            Codex::AttachedMaterialQuadlets | Codex::PathedMaterialQuadlets => {
+                Ok((self.body_size()? / 4) as u32)
             }
             _ => Ok(self.value().len() as u32),
         }
@@ -55,11 +57,12 @@ pub trait Group<T: GroupItem> {
 
     /// Get total size of the group
     fn full_size(&self) -> ParsideResult<usize> {
-        let mut size = match Self::CODE {
-            Codex::AttachedMaterialQuadlets | Codex::BigAttachedMaterialQuadlets => 4usize,
-            _ => self.counter()?.full_size()?,
-        };
+        Ok(self.counter()?.full_size()? + self.body_size()?)
+    }
 
+    /// Get size of the group without its counter
+    fn body_size(&self) -> ParsideResult<usize> {
+        let mut size = 0;
         for value in self.value().iter() {
             size += value.full_size()?;
         }
@@ -1,37 +1,51 @@
 pub mod attached_material_quadlets;
 pub mod controller_idx_sigs;
+pub mod essr_payloads;
 pub mod first_seen_replay_couples;
+#[cfg(test)]
+pub(crate) mod fixtures;
 pub mod group;
 pub mod non_trans_receipt_couples;
 pub mod pathed_material_quadlets;
 pub mod sad_path_sig;
 pub mod sad_path_sig_group;
 pub mod seal_source_couples;
+pub mod seal_source_triples;
 pub mod trans_idx_sig_groups;
 pub mod trans_last_idx_sig_groups;
 pub mod trans_receipt_quadruples;
 pub mod witness_idx_sigs;
 
-use crate::error::{ParsideError, ParsideResult};
+use crate::error::{ParsideError, ParsideResult, PathSegment};
 use crate::message::cold_code::ColdCode;
+use crate::message::counter::Counter;
+use crate::message::genus_version::GenusVersion;
 use crate::message::parsers::Parsers;
+use cesride::Matter;
+use serde::{Deserialize, Serialize};
 
 pub use self::attached_material_quadlets::AttachedMaterialQuadlets;
 pub use self::controller_idx_sigs::{ControllerIdxSig, ControllerIdxSigs};
+pub use self::essr_payloads::{EssrPayload, EssrPayloads};
 pub use self::first_seen_replay_couples::{FirstSeenReplayCouple, FirstSeenReplayCouples};
 pub use self::group::{Group, GroupItem};
 pub use self::non_trans_receipt_couples::{NonTransReceiptCouple, NonTransReceiptCouples};
-pub use self::pathed_material_quadlets::{PathedMaterialQuadlet, PathedMaterialQuadlets};
+pub use self::pathed_material_quadlets::PathedMaterialQuadlets;
 pub use self::sad_path_sig::{SadPathSig, SadPathSigs};
 pub use self::sad_path_sig_group::{SadPathSigGroup, SadPathSigGroups};
 pub use self::seal_source_couples::{SealSourceCouple, SealSourceCouples};
+pub use self::seal_source_triples::{SealSourceTriple, SealSourceTriples};
 pub use self::trans_idx_sig_groups::{TransIdxSigGroup, TransIdxSigGroups};
 pub use self::trans_last_idx_sig_groups::{TransLastIdxSigGroup, TransLastIdxSigGroups};
 pub use self::trans_receipt_quadruples::{TransReceiptQuadruple, TransReceiptQuadruples};
 pub use self::witness_idx_sigs::{WitnessIdxSig, WitnessIdxSigs};
 
+/// Maximum number of levels of groups nested in other groups, deeper streams are rejected
+/// before exhausting the stack
+pub(crate) const MAX_NESTING_DEPTH: usize = 16;
+
 /// Datastructures representing known CESR group
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum CesrGroup {
     ControllerIdxSigsVariant { value: ControllerIdxSigs },
     WitnessIdxSigsVariant { value: WitnessIdxSigs },
@@ -41,97 +55,305 @@ pub enum CesrGroup {
     TransLastIdxSigGroupsVariant { value: TransLastIdxSigGroups },
     FirstSeenReplayCouplesVariant { value: FirstSeenReplayCouples },
     SealSourceCouplesVariant { value: SealSourceCouples },
+    SealSourceTriplesVariant { value: SealSourceTriples },
     AttachedMaterialQuadletsVariant { value: AttachedMaterialQuadlets },
     SadPathSigGroupVariant { value: SadPathSigGroups },
     SadPathSigVariant { value: SadPathSigs },
     PathedMaterialQuadletsVariant { value: PathedMaterialQuadlets },
+    EssrPayloadsVariant { value: EssrPayloads },
 }
 
 impl CesrGroup {
     /// Parse CESR group from bytes
     pub fn from_stream_bytes(bytes: &[u8]) -> ParsideResult<(&[u8], CesrGroup)> {
+        Self::from_stream_bytes_with_version(bytes, &GenusVersion::default())
+    }
+
+    /// Parse CESR group from bytes using the counter code table of `version`
+    pub fn from_stream_bytes_with_version<'a>(
+        bytes: &'a [u8],
+        version: &GenusVersion,
+    ) -> ParsideResult<(&'a [u8], CesrGroup)> {
+        Self::from_nested_stream_bytes(bytes, 0, version).map_err(|err| err.located(bytes.len()))
+    }
+
+    /// Parse CESR group nested in `depth` enclosing groups
+    pub(crate) fn from_nested_stream_bytes<'a>(
+        bytes: &'a [u8],
+        depth: usize,
+        version: &GenusVersion,
+    ) -> ParsideResult<(&'a [u8], CesrGroup)> {
+        if depth >= MAX_NESTING_DEPTH {
+            return Err(ParsideError::Unexpected(format!(
+                "Groups nested more than {MAX_NESTING_DEPTH} levels deep"
+            )));
+        }
         if bytes.is_empty() {
             return Err(ParsideError::EmptyBytesStream);
         }
 
         let cold_code = ColdCode::try_from(bytes[0])?;
         let (rest, counter) = Parsers::counter_parser(&cold_code)?(bytes)?;
-        match counter.code().as_str() {
+        let small_code = counter.small_code();
+        let big = small_code != counter.code();
+        let code = version
+            .group_code(&small_code)
+            .filter(|_| !big || version.big_code(&small_code) == Some(counter.code()))
+            .ok_or_else(|| {
+                ParsideError::Unexpected(format!(
+                    "Unexpected counter code {:?} of {version} table",
+                    counter.code()
+                ))
+            })?;
+        let counter = if version.counts_quadlets() { counter.counting_quadlets() } else { counter };
+        Self::from_counted_stream_bytes(rest, code, &counter, &cold_code, depth, version).map_err(
+            |err| match Self::group_name(code) {
+                Some(name) => err.within(PathSegment::Name(name), bytes).with_code(&counter.code()),
+                None => err,
+            },
+        )
+    }
+
+    /// Parse group with KERI 1.0 `code` counted by `counter`
+    fn from_counted_stream_bytes<'a>(
+        rest: &'a [u8],
+        code: &str,
+        counter: &Counter,
+        cold_code: &ColdCode,
+        depth: usize,
+        version: &GenusVersion,
+    ) -> ParsideResult<(&'a [u8], CesrGroup)> {
+        match code {
             AttachedMaterialQuadlets::CODE => {
-                let (rest, group) =
-                    AttachedMaterialQuadlets::from_stream_bytes(rest, &counter, &cold_code)?;
+                let (rest, group) = AttachedMaterialQuadlets::from_stream_bytes(
+                    rest, counter, cold_code, depth, version,
+                )?;
                 Ok((rest, CesrGroup::AttachedMaterialQuadletsVariant { value: group }))
             }
             ControllerIdxSigs::CODE => {
-                let (rest, group) =
-                    ControllerIdxSigs::from_stream_bytes(rest, &counter, &cold_code)?;
+                let (rest, group) = ControllerIdxSigs::from_stream_bytes(rest, counter, cold_code)?;
                 Ok((rest, CesrGroup::ControllerIdxSigsVariant { value: group }))
             }
             WitnessIdxSigs::CODE => {
-                let (rest, group) = WitnessIdxSigs::from_stream_bytes(rest, &counter, &cold_code)?;
+                let (rest, group) = WitnessIdxSigs::from_stream_bytes(rest, counter, cold_code)?;
                 Ok((rest, CesrGroup::WitnessIdxSigsVariant { value: group }))
             }
             NonTransReceiptCouples::CODE => {
                 let (rest, group) =
-                    NonTransReceiptCouples::from_stream_bytes(rest, &counter, &cold_code)?;
+                    NonTransReceiptCouples::from_stream_bytes(rest, counter, cold_code)?;
                 Ok((rest, CesrGroup::NonTransReceiptCouplesVariant { value: group }))
             }
             TransReceiptQuadruples::CODE => {
                 let (rest, group) =
-                    TransReceiptQuadruples::from_stream_bytes(rest, &counter, &cold_code)?;
+                    TransReceiptQuadruples::from_stream_bytes(rest, counter, cold_code)?;
                 Ok((rest, CesrGroup::TransReceiptQuadruplesVariant { value: group }))
             }
             TransIdxSigGroups::CODE => {
-                let (rest, group) =
-                    TransIdxSigGroups::from_stream_bytes(rest, &counter, &cold_code)?;
+                let (rest, group) = TransIdxSigGroups::from_stream_bytes(rest, counter, cold_code)?;
                 Ok((rest, CesrGroup::TransIdxSigGroupsVariant { value: group }))
             }
             TransLastIdxSigGroups::CODE => {
                 let (rest, group) =
-                    TransLastIdxSigGroups::from_stream_bytes(rest, &counter, &cold_code)?;
+                    TransLastIdxSigGroups::from_stream_bytes(rest, counter, cold_code)?;
                 Ok((rest, CesrGroup::TransLastIdxSigGroupsVariant { value: group }))
             }
             FirstSeenReplayCouples::CODE => {
                 let (rest, group) =
-                    FirstSeenReplayCouples::from_stream_bytes(rest, &counter, &cold_code)?;
+                    FirstSeenReplayCouples::from_stream_bytes(rest, counter, cold_code)?;
                 Ok((rest, CesrGroup::FirstSeenReplayCouplesVariant { value: group }))
             }
             SealSourceCouples::CODE => {
-                let (rest, group) =
-                    SealSourceCouples::from_stream_bytes(rest, &counter, &cold_code)?;
+                let (rest, group) = SealSourceCouples::from_stream_bytes(rest, counter, cold_code)?;
                 Ok((rest, CesrGroup::SealSourceCouplesVariant { value: group }))
             }
+            SealSourceTriples::CODE => {
+                let (rest, group) = SealSourceTriples::from_stream_bytes(rest, counter, cold_code)?;
+                Ok((rest, CesrGroup::SealSourceTriplesVariant { value: group }))
+            }
             SadPathSigGroups::CODE => {
                 let (rest, group) =
-                    SadPathSigGroups::from_stream_bytes(rest, &counter, &cold_code)?;
+                    SadPathSigGroups::from_stream_bytes(rest, counter, cold_code, depth, version)?;
                 Ok((rest, CesrGroup::SadPathSigGroupVariant { value: group }))
             }
             SadPathSigs::CODE => {
-                let (rest, group) = SadPathSigs::from_stream_bytes(rest, &counter, &cold_code)?;
+                let (rest, group) = SadPathSigs::from_stream_bytes(rest, counter, cold_code)?;
                 Ok((rest, CesrGroup::SadPathSigVariant { value: group }))
             }
             PathedMaterialQuadlets::CODE => {
-                let (rest, group) =
-                    PathedMaterialQuadlets::from_stream_bytes(rest, &counter, &cold_code)?;
+                let (rest, group) = PathedMaterialQuadlets::from_stream_bytes(
+                    rest, counter, cold_code, depth, version,
+                )?;
                 Ok((rest, CesrGroup::PathedMaterialQuadletsVariant { value: group }))
             }
+            EssrPayloads::CODE => {
+                let (rest, group) = EssrPayloads::from_stream_bytes(rest, counter, cold_code)?;
+                Ok((rest, CesrGroup::EssrPayloadsVariant { value: group }))
+            }
             _ => Err(ParsideError::Unexpected(format!(
                 "Unexpected counter code {:?}",
                 counter.code()
             ))),
         }
     }
+
+    /// Name of the group with KERI 1.0 `code`, used in the path of parse errors
+    pub(crate) fn group_name(code: &str) -> Option<&'static str> {
+        let name = match code {
+            AttachedMaterialQuadlets::CODE => "AttachedMaterialQuadlets",
+            ControllerIdxSigs::CODE => "ControllerIdxSigs",
+            WitnessIdxSigs::CODE => "WitnessIdxSigs",
+            NonTransReceiptCouples::CODE => "NonTransReceiptCouples",
+            TransReceiptQuadruples::CODE => "TransReceiptQuadruples",
+            TransIdxSigGroups::CODE => "TransIdxSigGroups",
+            TransLastIdxSigGroups::CODE => "TransLastIdxSigGroups",
+            FirstSeenReplayCouples::CODE => "FirstSeenReplayCouples",
+            SealSourceCouples::CODE => "SealSourceCouples",
+            SealSourceTriples::CODE => "SealSourceTriples",
+            SadPathSigGroups::CODE => "SadPathSigGroups",
+            SadPathSigs::CODE => "SadPathSigs",
+            PathedMaterialQuadlets::CODE => "PathedMaterialQuadlets",
+            EssrPayloads::CODE => "EssrPayloads",
+            _ => return None,
+        };
+        Some(name)
+    }
+
+    /// Get qb64b representation of the group with the counter codes of `version`. Groups of
+    /// CESR 2.0 tables, nested ones included, count the quadlets they frame rather than items.
+    pub fn qb64b_with_version(&self, version: &GenusVersion) -> ParsideResult<Vec<u8>> {
+        if !version.counts_quadlets() {
+            return self.qb64b();
+        }
+
+        let mut body = vec![];
+        let code = match self {
+            CesrGroup::TransIdxSigGroupsVariant { value } => {
+                for item in &value.value {
+                    body.extend_from_slice(&item.prefixer.qb64b()?);
+                    body.extend_from_slice(&item.seqner.qb64b()?);
+                    body.extend_from_slice(&item.saider.qb64b()?);
+                    Self::extend_framed(&mut body, &item.isigers, version)?;
+                }
+                TransIdxSigGroups::CODE
+            }
+            CesrGroup::TransLastIdxSigGroupsVariant { value } => {
+                for item in &value.value {
+                    body.extend_from_slice(&item.prefixer.qb64b()?);
+                    Self::extend_framed(&mut body, &item.isigers, version)?;
+                }
+                TransLastIdxSigGroups::CODE
+            }
+            CesrGroup::AttachedMaterialQuadletsVariant { value } => {
+                for group in &value.value {
+                    body.extend_from_slice(&group.qb64b_with_version(version)?);
+                }
+                AttachedMaterialQuadlets::CODE
+            }
+            CesrGroup::PathedMaterialQuadletsVariant { value } => {
+                body.extend_from_slice(&value.pather.qb64b()?);
+                for group in &value.value {
+                    body.extend_from_slice(&group.qb64b_with_version(version)?);
+                }
+                PathedMaterialQuadlets::CODE
+            }
+            CesrGroup::SadPathSigGroupVariant { .. } | CesrGroup::SadPathSigVariant { .. } => {
+                return Err(ParsideError::Unexpected(format!(
+                    "No counter code for the group in {version} table"
+                )));
+            }
+            // items without nested groups encode the same in every table
+            CesrGroup::ControllerIdxSigsVariant { value } => Self::items(&mut body, value)?,
+            CesrGroup::WitnessIdxSigsVariant { value } => Self::items(&mut body, value)?,
+            CesrGroup::NonTransReceiptCouplesVariant { value } => Self::items(&mut body, value)?,
+            CesrGroup::TransReceiptQuadruplesVariant { value } => Self::items(&mut body, value)?,
+            CesrGroup::FirstSeenReplayCouplesVariant { value } => Self::items(&mut body, value)?,
+            CesrGroup::SealSourceCouplesVariant { value } => Self::items(&mut body, value)?,
+            CesrGroup::SealSourceTriplesVariant { value } => Self::items(&mut body, value)?,
+            CesrGroup::EssrPayloadsVariant { value } => Self::items(&mut body, value)?,
+        };
+        Self::framed(code, body, version)
+    }
+
+    /// Get qb2 representation of the group with the counter codes of `version`
+    pub fn qb2_with_version(&self, version: &GenusVersion) -> ParsideResult<Vec<u8>> {
+        Counter::qb64b_to_qb2(&self.qb64b_with_version(version)?)
+    }
+
+    /// Prefix `body` with counter of `version` tables for the group with KERI 1.0 `code`
+    fn framed(code: &str, body: Vec<u8>, version: &GenusVersion) -> ParsideResult<Vec<u8>> {
+        let code = version.table_code(code).ok_or_else(|| {
+            ParsideError::Unexpected(format!("No counter code for {code:?} in {version} table"))
+        })?;
+        let counter =
+            Counter::new_with_small_code_and_count(code, (body.len() / 4) as u32, version)?;
+        Ok([counter.qb64b()?, body].concat())
+    }
+
+    /// Append items of the group nested in a group item, framed by counter of `version` tables
+    fn extend_framed<T: GroupItem, G: Group<T>>(
+        out: &mut Vec<u8>,
+        group: &G,
+        version: &GenusVersion,
+    ) -> ParsideResult<()> {
+        let mut body = vec![];
+        let code = Self::items(&mut body, group)?;
+        out.extend_from_slice(&Self::framed(code, body, version)?);
+        Ok(())
+    }
+
+    /// Append qb64b of the items of `group` to `out`, returning the KERI 1.0 code of the group
+    fn items<T: GroupItem, G: Group<T>>(
+        out: &mut Vec<u8>,
+        group: &G,
+    ) -> ParsideResult<&'static str> {
+        for item in group.value() {
+            out.extend_from_slice(&item.qb64b()?);
+        }
+        Ok(G::CODE)
+    }
+
+    /// Parse CESR groups filling exactly the quadlets of `frame` following `offset`.
+    /// Offsets of errors are counted from the end of `frame`, see [`ParsideError::shifted`].
+    pub(crate) fn from_framed_bytes(
+        frame: &[u8],
+        offset: usize,
+        cold_code: &ColdCode,
+        depth: usize,
+        version: &GenusVersion,
+    ) -> ParsideResult<Vec<CesrGroup>> {
+        let mut rest = &frame[offset..];
+        let mut groups = vec![];
+        while !rest.is_empty() {
+            let (next, group) = CesrGroup::from_nested_stream_bytes(rest, depth, version).map_err(
+                |err| match err {
+                    ParsideError::Incomplete(_) => ParsideError::QuadletCountMismatch {
+                        expected: frame.len() / cold_code.quadlet_size(),
+                        actual: (frame.len() - rest.len()) / cold_code.quadlet_size(),
+                    },
+                    err => err.within(PathSegment::Index(groups.len()), rest),
+                },
+            )?;
+            groups.push(group);
+            rest = next;
+        }
+        Ok(groups)
+    }
 }
 
 #[cfg(test)]
 pub mod tests {
     use super::*;
+    use crate::message::counter::{self, Counter};
+    use crate::message::Texter;
+    use crate::{Message, MessageList};
     pub use cesride::matter::Codex as MatterCodex;
-    use cesride::{Indexer, Matter};
+    use cesride::{Indexer, Matter, Pather};
+
+    pub use super::fixtures::*;
 
     #[test]
     pub fn test_parse_trans_idx_sig_groups() {
-        let stream = br#"-FABEFhg5my9DuMU6gw1CVk6QgkmZKBttWSXDzVzWVmxh0_K0AAAAAAAAAAAAAAAAAAAAAAAEFhg5my9DuMU6gw1CVk6QgkmZKBttWSXDzVzWVmxh0_K-AABAADghKct9eYTuSgSd5wdPSYG06tGX7ZRp_BDnrgbSxJpsJtrA-fP7Pa1W602gHeMrO6HZsD1z3tWV5jGlApFmVIB"#;
+        let stream = TRANS_IDX_SIG_GROUPS;
         let (rest, group) = CesrGroup::from_stream_bytes(stream).unwrap();
         assert!(rest.is_empty());
         match group {
@@ -147,7 +369,7 @@ pub mod tests {
 
     #[test]
     pub fn test_parse_controller_idx_sigs() {
-        let stream = br#"-AABAABg3q8uNg1A2jhEAdbKGf-QupQhNnmZQx3zIyPLWBe6qqLT5ynytivf9EwJhxyhy87a0x2cezDdil4SsM2xxs0O"#;
+        let stream = CONTROLLER_IDX_SIGS;
         let (rest, group) = CesrGroup::from_stream_bytes(stream).unwrap();
         assert!(rest.is_empty());
         match group {
@@ -161,7 +383,7 @@ pub mod tests {
 
     #[test]
     pub fn test_parse_non_trans_receipt_couples() {
-        let stream = br#"-CABBD8-gMSJ6K1PQ7_gG5ZJn2NkHQJgdkiNrTBz_FWWS_cC0BDc1i44ZX0jaIHh5oNDx-TITbPnI6VEn2nKlqPwkkTF452X7XxYh80tolDpReYwZpnD8TF4Or2v3CpSCikyt6EG"#;
+        let stream = NON_TRANS_RECEIPT_COUPLES;
         let (rest, group) = CesrGroup::from_stream_bytes(stream).unwrap();
         assert!(rest.is_empty());
         match group {
@@ -175,14 +397,14 @@ pub mod tests {
 
     #[test]
     pub fn test_parse_attached_material_quadlets() {
-        let stream = br#"-VA--AABAAAEmCc25ETG2m1Ya-tPGuEqsPywOtusQwXKy076ve56IHXzX2bs0xsdQ4dk0XsanstpThg71ynIy-yUDSue6jMD-BABAABfvC7zCIVOVMol9C4AlSALS9JhL8PCdfgRnJgkXG4U11gFyZbsI_J828POrtwtoOmFhs20hoH1pYw4NZr2cdwN-EAB0AAAAAAAAAAAAAAAAAAAAAAE1AAG2023-02-07T15c00c00d025640p00c00"#;
+        let stream = ATTACHED_MATERIAL_QUADLETS;
         let (rest, message) = CesrGroup::from_stream_bytes(stream).unwrap();
         assert!(rest.is_empty());
         match message {
             CesrGroup::AttachedMaterialQuadletsVariant { value: group } => {
                 assert_eq!(3, group.value.len());
                 assert!(matches!(
-                    group.value.get(0).cloned().unwrap(),
+                    group.value.first().cloned().unwrap(),
                     CesrGroup::ControllerIdxSigsVariant { .. }
                 ));
                 assert!(matches!(
@@ -198,9 +420,144 @@ pub mod tests {
         }
     }
 
+    #[test]
+    pub fn test_parse_attached_material_quadlets_within_counted_quadlets() {
+        let stream = [ATTACHED_MATERIAL_QUADLETS, CONTROLLER_IDX_SIGS].concat();
+        let (rest, group) = CesrGroup::from_stream_bytes(&stream).unwrap();
+        assert_eq!(CONTROLLER_IDX_SIGS, rest);
+        match group {
+            CesrGroup::AttachedMaterialQuadletsVariant { value: group } => {
+                assert_eq!(3, group.value.len())
+            }
+            _ => panic!("Unexpected case"),
+        }
+    }
+
+    #[test]
+    pub fn test_parse_attached_material_quadlets_with_wrong_count() {
+        let mut stream = ATTACHED_MATERIAL_QUADLETS.to_vec();
+        stream[3] = b'9';
+        let err = CesrGroup::from_stream_bytes(&stream).unwrap_err();
+        assert_eq!(
+            &ParsideError::QuadletCountMismatch { expected: 61, actual: 46 },
+            err.root_cause()
+        );
+
+        let mut stream = [ATTACHED_MATERIAL_QUADLETS, CONTROLLER_IDX_SIGS].concat();
+        stream[3] = b'_';
+        let err = CesrGroup::from_stream_bytes(&stream).unwrap_err();
+        assert_eq!(
+            &ParsideError::QuadletCountMismatch { expected: 63, actual: 62 },
+            err.root_cause()
+        );
+    }
+
+    #[test]
+    pub fn test_parse_big_counters() {
+        let stream = [b"-0VAAAA-", &ATTACHED_MATERIAL_QUADLETS[4..]].concat();
+        let (rest, group) = CesrGroup::from_stream_bytes(&stream).unwrap();
+        assert!(rest.is_empty());
+        match group {
+            CesrGroup::AttachedMaterialQuadletsVariant { value: group } => {
+                assert_eq!(3, group.value.len())
+            }
+            _ => panic!("Unexpected case"),
+        }
+
+        // pathed material quadlets and ESSR payloads have big codes in the KERI 1.0 table too
+        let pathed = PathedMaterialQuadlets::new_with_pather(
+            Pather::new_with_qb64("5AABAA-a").unwrap(),
+            vec![CesrGroup::from_stream_bytes(CONTROLLER_IDX_SIGS).unwrap().1],
+        );
+        let qb64 = pathed.qb64b().unwrap();
+        let stream = [b"-0LAAA", &qb64[2..]].concat();
+        let (rest, group) = CesrGroup::from_stream_bytes(&stream).unwrap();
+        assert!(rest.is_empty());
+        assert!(matches!(group, CesrGroup::PathedMaterialQuadletsVariant { .. }));
+        let texter = Texter::new_with_code_and_raw(MatterCodex::Bytes_L0, b"payload").unwrap();
+        let qb64 = EssrPayloads::new(vec![EssrPayload::new(texter)]).qb64b().unwrap();
+        let stream = [b"-0ZAAA", &qb64[2..]].concat();
+        let (rest, group) = CesrGroup::from_stream_bytes(&stream).unwrap();
+        assert!(rest.is_empty());
+        assert!(matches!(group, CesrGroup::EssrPayloadsVariant { .. }));
+
+        // the KERI 1.0 table has no other big codes, the CESR 2.0 table has one for every group
+        let stream = [b"-0AAAAAB", &CONTROLLER_IDX_SIGS[4..]].concat();
+        assert!(CesrGroup::from_stream_bytes(&stream).is_err());
+        let version = GenusVersion::new(GenusVersion::KERI_ACDC, 2, 0);
+        let stream = [b"-0KAAAAW", &CONTROLLER_IDX_SIGS[4..]].concat();
+        let (rest, group) = CesrGroup::from_stream_bytes_with_version(&stream, &version).unwrap();
+        assert!(rest.is_empty());
+        assert!(matches!(group, CesrGroup::ControllerIdxSigsVariant { .. }));
+    }
+
+    #[test]
+    pub fn test_encode_big_counters_when_count_overflows() {
+        let (_, group) = CesrGroup::from_stream_bytes(CONTROLLER_IDX_SIGS).unwrap();
+        let sig = match group {
+            CesrGroup::ControllerIdxSigsVariant { value } => value.value[0].clone(),
+            _ => panic!("Unexpected case"),
+        };
+
+        // 4095 signatures fit the small code, attached material quadlets switch to the big one
+        let sigs = ControllerIdxSigs::new(vec![sig.clone(); 4095]);
+        assert_eq!(counter::Codex::ControllerIdxSigs, sigs.counter().unwrap().code());
+        let group = CesrGroup::AttachedMaterialQuadletsVariant {
+            value: AttachedMaterialQuadlets::new(vec![CesrGroup::ControllerIdxSigsVariant {
+                value: sigs,
+            }]),
+        };
+        let qb64 = group.qb64().unwrap();
+        assert!(qb64.starts_with("-0V"));
+        assert_eq!(qb64.len(), group.full_size().unwrap());
+
+        let (rest, parsed) = CesrGroup::from_stream_bytes(qb64.as_bytes()).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(qb64, parsed.qb64().unwrap());
+
+        let qb2 = group.qb2().unwrap();
+        let (rest, parsed) = CesrGroup::from_stream_bytes(&qb2).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(qb64, parsed.qb64().unwrap());
+
+        let group = CesrGroup::PathedMaterialQuadletsVariant {
+            value: PathedMaterialQuadlets::new_with_pather(
+                Pather::new_with_qb64("5AABAA-a").unwrap(),
+                vec![CesrGroup::ControllerIdxSigsVariant {
+                    value: ControllerIdxSigs::new(vec![sig.clone(); 4095]),
+                }],
+            ),
+        };
+        let qb64 = group.qb64().unwrap();
+        assert!(qb64.starts_with("-0L"));
+        let (rest, parsed) = CesrGroup::from_stream_bytes(qb64.as_bytes()).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(qb64, parsed.qb64().unwrap());
+
+        let texter = Texter::new_with_code_and_raw(MatterCodex::Bytes_L0, b"payload").unwrap();
+        let payloads = EssrPayloads::new(vec![EssrPayload::new(texter); 4096]);
+        assert_eq!(counter::Codex::BigESSRPayloadGroup, payloads.counter().unwrap().code());
+        let qb64 = payloads.qb64().unwrap();
+        let (rest, parsed) = CesrGroup::from_stream_bytes(qb64.as_bytes()).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(qb64, parsed.qb64().unwrap());
+
+        // the KERI 1.0 table has no big code for more signatures, the CESR 2.0 table has
+        let sigs = ControllerIdxSigs::new(vec![sig; 4096]);
+        assert!(sigs.counter().is_err());
+        let group = CesrGroup::ControllerIdxSigsVariant { value: sigs };
+        assert!(group.qb64b().is_err());
+        let version = GenusVersion::new(GenusVersion::KERI_ACDC, 2, 0);
+        let qb64b = group.qb64b_with_version(&version).unwrap();
+        assert!(qb64b.starts_with(b"-0K"));
+        let (rest, parsed) = CesrGroup::from_stream_bytes_with_version(&qb64b, &version).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(qb64b, parsed.qb64b_with_version(&version).unwrap());
+    }
+
     #[test]
     pub fn test_parse_trans_last_idx_sig_groups() {
-        let stream = br#"-HABEB1f36VmoizOIpBIBv3X4ZiWJQWjtKJ7TMmsZltT0B32-AABAAAKB9u6wyLS9kl_iGVGCqrs-3XqFbyGeOKuiOEA9JZpxI9GMv0GJv2wbY1-sOD_HOJcvXO7LSO8g8MSeRXjtL4I"#;
+        let stream = TRANS_LAST_IDX_SIG_GROUPS;
         let (rest, group) = CesrGroup::from_stream_bytes(stream).unwrap();
         assert!(rest.is_empty());
         match group {
@@ -216,6 +573,212 @@ pub mod tests {
     pub fn test_parse_when_there_is_enough_group_bytes() {
         let stream = br#"-FABEFhg5my9DuMU6gw1CVk6QgkmZKBttWS"#;
         let err = CesrGroup::from_stream_bytes(stream).unwrap_err();
-        assert!(matches!(err, ParsideError::StreamDeserializationError(..)));
+        assert!(matches!(err, ParsideError::Incomplete(..)));
+    }
+
+    /// Group `body` behind CESR 2.0 counter `code`, counting its quadlets
+    fn v2_group(code: &str, body: &[u8]) -> Vec<u8> {
+        let counter = Counter::new_with_code_and_count(code, body.len() as u32 / 4).unwrap();
+        [counter.qb64b().unwrap(), body.to_vec()].concat()
+    }
+
+    #[test]
+    pub fn test_parse_cesr_2_groups() {
+        let version = GenusVersion::new(GenusVersion::KERI_ACDC, 2, 0);
+        let sigs = v2_group(counter::CodexV2::ControllerIdxSigs, &CONTROLLER_IDX_SIGS[4..]);
+        let (rest, group) = CesrGroup::from_stream_bytes_with_version(&sigs, &version).unwrap();
+        assert!(rest.is_empty());
+        match group {
+            CesrGroup::ControllerIdxSigsVariant { value: group } => {
+                assert_eq!(1, group.value.len())
+            }
+            _ => panic!("Unexpected case"),
+        }
+
+        // nested signatures of the trans indexed signature group count quadlets too
+        let body = [&TRANS_IDX_SIG_GROUPS[4..116], &sigs].concat();
+        let groups = v2_group(counter::CodexV2::TransIdxSigGroups, &body);
+        let stream = v2_group(counter::CodexV2::AttachmentGroup, &[sigs, groups].concat());
+        let (rest, group) = CesrGroup::from_stream_bytes_with_version(&stream, &version).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(stream, group.qb64b_with_version(&version).unwrap());
+        let qb2 = group.qb2_with_version(&version).unwrap();
+        let (_, parsed) = CesrGroup::from_stream_bytes_with_version(&qb2, &version).unwrap();
+        assert_eq!(stream, parsed.qb64b_with_version(&version).unwrap());
+        match group {
+            CesrGroup::AttachedMaterialQuadletsVariant { value: group } => {
+                assert!(matches!(group.value[0], CesrGroup::ControllerIdxSigsVariant { .. }));
+                match &group.value[1] {
+                    CesrGroup::TransIdxSigGroupsVariant { value } => {
+                        assert_eq!(1, value.value.len());
+                        assert_eq!(1, value.value[0].isigers.value.len());
+                    }
+                    _ => panic!("Unexpected case"),
+                }
+            }
+            _ => panic!("Unexpected case"),
+        }
+
+        // codes of the 1.0 table mean other groups in the 2.0 table
+        assert!(CesrGroup::from_stream_bytes_with_version(CONTROLLER_IDX_SIGS, &version).is_err());
+    }
+
+    #[test]
+    pub fn test_parse_cesr_2_group_overrunning_counted_quadlets() {
+        let version = GenusVersion::new(GenusVersion::KERI_ACDC, 2, 0);
+        let body = [&CONTROLLER_IDX_SIGS[4..], &CONTROLLER_IDX_SIGS[4..48]].concat();
+        let stream = v2_group(counter::CodexV2::ControllerIdxSigs, &body);
+        let err = CesrGroup::from_stream_bytes_with_version(&stream, &version).unwrap_err();
+        assert!(matches!(err.root_cause(), ParsideError::Unexpected(..)));
+    }
+
+    #[test]
+    pub fn test_serde_round_trip() {
+        let concat = |parts: &[&[u8]]| parts.concat();
+        let prefixer = &TRANS_IDX_SIG_GROUPS[4..48];
+        let seqner = b"0AAAAAAAAAAAAAAAAAAAAAAB";
+        let saider = &TRANS_IDX_SIG_GROUPS[72..116];
+        let siger = &TRANS_IDX_SIG_GROUPS[120..];
+        let texter = Texter::new_with_code_and_raw(MatterCodex::Bytes_L0, b"payload").unwrap();
+        let fixtures = [
+            TRANS_IDX_SIG_GROUPS.to_vec(),
+            CONTROLLER_IDX_SIGS.to_vec(),
+            NON_TRANS_RECEIPT_COUPLES.to_vec(),
+            ATTACHED_MATERIAL_QUADLETS.to_vec(),
+            TRANS_LAST_IDX_SIG_GROUPS.to_vec(),
+            // sad path signature, alone and in a group
+            concat(&[b"-JAB5AABAA-a", TRANS_IDX_SIG_GROUPS]),
+            concat(&[b"-KAB6AABAAA--JAB5AABAA-a", TRANS_IDX_SIG_GROUPS]),
+            // seal source couples and triples
+            concat(&[b"-GAB", seqner, saider]),
+            concat(&[b"-IAB", prefixer, seqner, saider]),
+            // first seen replay couples
+            ATTACHED_MATERIAL_QUADLETS[188..].to_vec(),
+            // trans receipt quadruples
+            concat(&[b"-DAB", prefixer, seqner, saider, siger]),
+            // pathed material quadlets
+            concat(&[b"-LAZ5AABAA-a", CONTROLLER_IDX_SIGS]),
+            EssrPayloads::new(vec![EssrPayload::new(texter)]).qb64b().unwrap(),
+        ];
+        for fixture in fixtures {
+            let (rest, group) = CesrGroup::from_stream_bytes(&fixture).unwrap();
+            assert!(rest.is_empty());
+            let json = serde_json::to_string(&group).unwrap();
+            let parsed: CesrGroup = serde_json::from_str(&json).unwrap();
+            assert_eq!(fixture, parsed.qb64b().unwrap());
+        }
+
+        let (_, group) = CesrGroup::from_stream_bytes(NON_TRANS_RECEIPT_COUPLES).unwrap();
+        let json = serde_json::to_value(&group).unwrap();
+        let couple = &json["NonTransReceiptCouplesVariant"]["value"]["value"][0]["cigar"];
+        assert_eq!("0B", couple["code"]);
+        assert_eq!("B", couple["verfer"]["code"]);
+        assert_eq!(
+            &NON_TRANS_RECEIPT_COUPLES[4..48],
+            couple["verfer"]["qb64"].as_str().unwrap().as_bytes()
+        );
+
+        // code has to match the qb64 of the primitive
+        let json =
+            serde_json::to_string(&json).unwrap().replacen(r#""code":"B""#, r#""code":"D""#, 1);
+        assert!(serde_json::from_str::<CesrGroup>(&json).is_err());
+
+        // so does the qb64, trailing characters included
+        for (label, qb64) in [("prefixer", prefixer), ("siger", siger)] {
+            let (_, group) = CesrGroup::from_stream_bytes(TRANS_IDX_SIG_GROUPS).unwrap();
+            let json = serde_json::to_string(&group).unwrap();
+            let qb64 = std::str::from_utf8(qb64).unwrap();
+            let tampered = json.replacen(qb64, &format!("{qb64}JUNK"), 1);
+            assert_ne!(json, tampered, "{label}");
+            assert!(serde_json::from_str::<CesrGroup>(&tampered).is_err(), "{label}");
+        }
+        let json = serde_json::to_string(&group).unwrap();
+        let qb64 = std::str::from_utf8(&NON_TRANS_RECEIPT_COUPLES[48..]).unwrap();
+        let tampered = json.replacen(qb64, &format!("{qb64}JUNK"), 1);
+        assert!(serde_json::from_str::<CesrGroup>(&tampered).is_err());
+    }
+
+    #[test]
+    pub fn test_parse_malformed_groups_returns_error() {
+        let vectors = [
+            TRANS_IDX_SIG_GROUPS,
+            CONTROLLER_IDX_SIGS,
+            NON_TRANS_RECEIPT_COUPLES,
+            ATTACHED_MATERIAL_QUADLETS,
+            TRANS_LAST_IDX_SIG_GROUPS,
+        ];
+        let mut streams = vec![];
+        for vector in vectors {
+            let (_, group) = CesrGroup::from_stream_bytes(vector).unwrap();
+            streams.push(vector.to_vec());
+            streams.push(group.qb2().unwrap());
+        }
+
+        for stream in streams {
+            for size in 0..stream.len() {
+                assert!(CesrGroup::from_stream_bytes(&stream[..size]).is_err());
+            }
+            for index in 0..stream.len() {
+                for byte in [0x00, 0xdf, 0xff, b'-', b'0', b'5', b'9', b'A', b'_', b'{'] {
+                    let mut stream = stream.clone();
+                    stream[index] = byte;
+                    let _ = CesrGroup::from_stream_bytes(&stream);
+                    let _ = Message::from_stream_bytes(&stream);
+                    let _ = MessageList::from_stream_bytes(&stream);
+                }
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_parse_empty_lead_bytes_material() {
+        let stream = br#"-LAB5AAA"#;
+        assert!(CesrGroup::from_stream_bytes(stream).is_err());
+    }
+
+    #[test]
+    pub fn test_parse_too_deeply_nested_groups() {
+        let mut stream = String::new();
+        for _ in 0..=MAX_NESTING_DEPTH {
+            let count = Counter::new_with_code_and_count(
+                PathedMaterialQuadlets::CODE,
+                (stream.len() as u32 + 8) / 4,
+            )
+            .unwrap();
+            stream = format!("{}5AABAA-a{}", count.qb64().unwrap(), stream);
+        }
+        let err = CesrGroup::from_stream_bytes(stream.as_bytes()).unwrap_err();
+        assert!(matches!(err.root_cause(), ParsideError::Unexpected(..)));
+    }
+
+    #[test]
+    pub fn test_parse_error_locates_failing_item() {
+        // signature of the first group in TransIdxSigGroups starts at byte 120
+        let mut trans_idx_sig_groups = TRANS_IDX_SIG_GROUPS.to_vec();
+        trans_idx_sig_groups[120] = b'#';
+
+        let err = CesrGroup::from_stream_bytes(&trans_idx_sig_groups).unwrap_err();
+        assert_eq!(
+            "TransIdxSigGroups[0].isigers[0]: invalid Siger (unexpected byte 0x23 in Base64 material) at offset 120",
+            err.to_string()
+        );
+
+        let body = [CONTROLLER_IDX_SIGS, &trans_idx_sig_groups].concat();
+        let counter = Counter::new_with_code_and_count(
+            AttachedMaterialQuadlets::CODE,
+            (body.len() / 4) as u32,
+        )
+        .unwrap();
+        let stream = [&counter.qb64b().unwrap(), &body[..]].concat();
+        match CesrGroup::from_stream_bytes(&stream).unwrap_err() {
+            ParsideError::Parse { path, code, index, offset, source } => {
+                assert_eq!("AttachedMaterialQuadlets[1].TransIdxSigGroups[0].isigers[0]", path);
+                assert_eq!(Some(counter::Codex::ControllerIdxSigs.to_string()), code);
+                assert_eq!(Some(0), index);
+                assert_eq!(4 + CONTROLLER_IDX_SIGS.len() + 120, offset);
+                assert!(matches!(*source, ParsideError::InvalidPrimitive { .. }));
+            }
+            err => panic!("Unexpected error {err:?}"),
+        }
     }
 }
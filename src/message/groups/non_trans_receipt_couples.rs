@@ -1,12 +1,13 @@
-use crate::error::{ParsideError, ParsideResult};
+use crate::error::ParsideResult;
 use crate::message::cold_code::ColdCode;
+use crate::message::counter::{Codex, Counter};
 use crate::message::parsers::Parsers;
 use crate::message::{Group, GroupItem};
-use cesride::counter::Codex;
-use cesride::{Cigar, Counter, Matter};
-use nom::multi::count;
+use crate::utils::nom::counted;
+use cesride::{Cigar, Matter};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct NonTransReceiptCouples {
     pub value: Vec<NonTransReceiptCouple>,
 }
@@ -29,15 +30,15 @@ impl NonTransReceiptCouples {
         counter: &Counter,
         cold_code: &ColdCode,
     ) -> ParsideResult<(&'a [u8], NonTransReceiptCouples)> {
-        let (rest, body) =
-            count(Parsers::cigar_parser(cold_code)?, counter.count() as usize)(bytes)?;
+        let (rest, body) = counted(Parsers::cigar_parser(cold_code)?, counter, cold_code)(bytes)?;
         let body = body.into_iter().map(|cigar| NonTransReceiptCouple { cigar }).collect();
         Ok((rest, NonTransReceiptCouples { value: body }))
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct NonTransReceiptCouple {
+    #[serde(with = "crate::utils::serde::cigar")]
     pub cigar: Cigar,
 }
 
@@ -49,19 +50,26 @@ impl NonTransReceiptCouple {
 
 impl GroupItem for NonTransReceiptCouple {
     fn qb64(&self) -> ParsideResult<String> {
-        self.cigar.qb64().map_err(ParsideError::from)
+        let mut out = String::new();
+        out += &self.cigar.verfer().qb64()?;
+        out += &self.cigar.qb64()?;
+        Ok(out)
     }
 
     fn qb64b(&self) -> ParsideResult<Vec<u8>> {
-        self.cigar.qb64b().map_err(ParsideError::from)
+        let mut out = self.cigar.verfer().qb64b()?;
+        out.extend_from_slice(&self.cigar.qb64b()?);
+        Ok(out)
     }
 
     fn qb2(&self) -> ParsideResult<Vec<u8>> {
-        self.cigar.qb2().map_err(ParsideError::from)
+        let mut out = self.cigar.verfer().qb2()?;
+        out.extend_from_slice(&self.cigar.qb2()?);
+        Ok(out)
     }
 
     fn full_size(&self) -> ParsideResult<usize> {
-        let size = self.cigar.full_size()?;
+        let size = self.cigar.verfer().full_size()? + self.cigar.full_size()?;
         Ok(size)
     }
 }
@@ -75,13 +83,32 @@ pub mod tests {
     pub fn test_parse_non_trans_receipt_couples() {
         let stream = br#"BD8-gMSJ6K1PQ7_gG5ZJn2NkHQJgdkiNrTBz_FWWS_cC0BDc1i44ZX0jaIHh5oNDx-TITbPnI6VEn2nKlqPwkkTF452X7XxYh80tolDpReYwZpnD8TF4Or2v3CpSCikyt6EG"#;
 
-        let counter =
-            Counter::new(Some(1), None, Some(NonTransReceiptCouples::CODE), None, None, None)
-                .unwrap();
+        let counter = Counter::new_with_code_and_count(NonTransReceiptCouples::CODE, 1).unwrap();
         let (rest, group) =
             NonTransReceiptCouples::from_stream_bytes(stream, &counter, &ColdCode::CtB64).unwrap();
         assert!(rest.is_empty());
         assert_eq!(1, group.value.len());
         assert_eq!(MatterCodex::Ed25519_Sig.to_string(), group.value[0].cigar.code());
     }
+
+    #[test]
+    pub fn test_encode_non_trans_receipt_couples() {
+        let stream = br#"BD8-gMSJ6K1PQ7_gG5ZJn2NkHQJgdkiNrTBz_FWWS_cC0BDc1i44ZX0jaIHh5oNDx-TITbPnI6VEn2nKlqPwkkTF452X7XxYh80tolDpReYwZpnD8TF4Or2v3CpSCikyt6EG"#;
+
+        // couples are encoded with the verfer of their cigar
+        let counter = Counter::new_with_code_and_count(NonTransReceiptCouples::CODE, 1).unwrap();
+        let (_, group) =
+            NonTransReceiptCouples::from_stream_bytes(stream, &counter, &ColdCode::CtB64).unwrap();
+        let item = &group.value[0];
+        assert_eq!(stream.len(), item.full_size().unwrap());
+        assert_eq!(stream.to_vec(), item.qb64b().unwrap());
+        assert_eq!(stream.to_vec(), item.qb64().unwrap().into_bytes());
+
+        let qb2 = item.qb2().unwrap();
+        assert_eq!(stream.len() / 4 * 3, qb2.len());
+        let (rest, group) =
+            NonTransReceiptCouples::from_stream_bytes(&qb2, &counter, &ColdCode::CtOpB2).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(stream.to_vec(), group.value[0].qb64b().unwrap());
+    }
 }
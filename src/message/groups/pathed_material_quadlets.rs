@@ -1,63 +1,144 @@
 use crate::error::{ParsideError, ParsideResult};
 use crate::message::cold_code::ColdCode;
+use crate::message::counter::{Codex, Counter};
+use crate::message::genus_version::GenusVersion;
+use crate::message::parsers::Parsers;
 use crate::message::{Group, GroupItem};
-use cesride::counter::Codex;
-use cesride::{Counter, Indexer, Siger};
+use crate::utils::nom::field;
+use crate::CesrGroup;
+use cesride::{Matter, Pather};
+use serde::{Deserialize, Serialize};
 
-// FIXME: Implement proper definition
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PathedMaterialQuadlets {
-    pub value: Vec<PathedMaterialQuadlet>,
+    #[serde(with = "crate::utils::serde::matter")]
+    pub pather: Pather,
+    pub value: Vec<CesrGroup>,
 }
 
-impl Group<PathedMaterialQuadlet> for PathedMaterialQuadlets {
+impl Group<CesrGroup> for PathedMaterialQuadlets {
     const CODE: &'static str = Codex::PathedMaterialQuadlets;
 
-    fn new(value: Vec<PathedMaterialQuadlet>) -> Self {
-        Self { value }
+    fn new(value: Vec<CesrGroup>) -> Self {
+        Self { pather: Pather::default(), value }
     }
 
-    fn value(&self) -> &Vec<PathedMaterialQuadlet> {
+    fn value(&self) -> &Vec<CesrGroup> {
         &self.value
     }
+
+    fn qb64(&self) -> ParsideResult<String> {
+        let mut out = self.counter()?.qb64()?;
+        out.push_str(&self.pather.qb64()?);
+        for value in self.value().iter() {
+            out.push_str(&value.qb64()?);
+        }
+        Ok(out)
+    }
+
+    fn qb64b(&self) -> ParsideResult<Vec<u8>> {
+        let mut out = self.counter()?.qb64b()?;
+        out.extend_from_slice(&self.pather.qb64b()?);
+        for value in self.value().iter() {
+            out.extend_from_slice(&value.qb64b()?);
+        }
+        Ok(out)
+    }
+
+    fn qb2(&self) -> ParsideResult<Vec<u8>> {
+        let mut out = self.counter()?.qb2()?;
+        out.extend_from_slice(&self.pather.qb2()?);
+        for value in self.value().iter() {
+            out.extend_from_slice(&value.qb2()?);
+        }
+        Ok(out)
+    }
+
+    fn body_size(&self) -> ParsideResult<usize> {
+        let mut size = self.pather.full_size()?;
+        for value in self.value().iter() {
+            size += value.full_size()?;
+        }
+        Ok(size)
+    }
 }
 
 impl PathedMaterialQuadlets {
+    pub fn new_with_pather(pather: Pather, value: Vec<CesrGroup>) -> Self {
+        Self { pather, value }
+    }
+
     pub(crate) fn from_stream_bytes<'a>(
-        _bytes: &'a [u8],
-        _counter: &Counter,
-        _cold_code: &ColdCode,
+        bytes: &'a [u8],
+        counter: &Counter,
+        cold_code: &ColdCode,
+        depth: usize,
+        version: &GenusVersion,
     ) -> ParsideResult<(&'a [u8], PathedMaterialQuadlets)> {
-        unimplemented!();
-    }
-}
+        let size = counter.count() as usize * cold_code.quadlet_size();
+        if bytes.len() < size {
+            return Err(ParsideError::Incomplete(size - bytes.len()));
+        }
 
-#[derive(Debug, Clone, Default)]
-pub struct PathedMaterialQuadlet {
-    pub siger: Siger,
-}
+        let (frame, rest) = bytes.split_at(size);
+        let (material, pather) = match field("pather", Parsers::pather_parser(cold_code)?)(frame) {
+            Ok(value) => value,
+            Err(nom::Err::Incomplete(_)) => {
+                return Err(ParsideError::QuadletCountMismatch {
+                    expected: counter.count() as usize,
+                    actual: 0,
+                })
+            }
+            Err(err) => return Err(ParsideError::from(err).shifted(rest.len())),
+        };
+        let offset = frame.len() - material.len();
+        let body = CesrGroup::from_framed_bytes(frame, offset, cold_code, depth + 1, version)
+            .map_err(|err| err.shifted(rest.len()))?;
 
-impl PathedMaterialQuadlet {
-    pub fn new(siger: Siger) -> Self {
-        Self { siger }
+        Ok((rest, PathedMaterialQuadlets { pather, value: body }))
     }
 }
 
-impl GroupItem for PathedMaterialQuadlet {
-    fn qb64(&self) -> ParsideResult<String> {
-        self.siger.qb64().map_err(ParsideError::from)
-    }
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use cesride::Bext;
 
-    fn qb64b(&self) -> ParsideResult<Vec<u8>> {
-        self.siger.qb64b().map_err(ParsideError::from)
-    }
+    const PATHED_MATERIAL_QUADLETS: &str = concat!(
+        "-LA75AABAA-a",
+        "-AABAABg3q8uNg1A2jhEAdbKGf-QupQhNnmZQx3zIyPLWBe6qqLT5ynytivf9EwJhxyhy87a0x2cezDdil4SsM2xxs0O",
+        "-CABBD8-gMSJ6K1PQ7_gG5ZJn2NkHQJgdkiNrTBz_FWWS_cC0BDc1i44ZX0jaIHh5oNDx-TITbPnI6VEn2nKlqPwkkTF452X7XxYh80tolDpReYwZpnD8TF4Or2v3CpSCikyt6EG"
+    );
 
-    fn qb2(&self) -> ParsideResult<Vec<u8>> {
-        self.siger.qb2().map_err(ParsideError::from)
+    #[test]
+    pub fn test_parse_pathed_material_quadlets() {
+        let stream = format!("{}{}", PATHED_MATERIAL_QUADLETS, "-AAB");
+        let (rest, group) = CesrGroup::from_stream_bytes(stream.as_bytes()).unwrap();
+        assert_eq!(b"-AAB", rest);
+        match &group {
+            CesrGroup::PathedMaterialQuadletsVariant { value } => {
+                assert_eq!("-a", value.pather.bext().unwrap());
+                assert_eq!(2, value.value.len());
+                assert!(matches!(value.value[0], CesrGroup::ControllerIdxSigsVariant { .. }));
+                assert!(matches!(value.value[1], CesrGroup::NonTransReceiptCouplesVariant { .. }));
+            }
+            _ => panic!("Unexpected case"),
+        }
+
+        assert_eq!(PATHED_MATERIAL_QUADLETS, group.qb64().unwrap());
+        let qb2 = group.qb2().unwrap();
+        let (rest, group) = CesrGroup::from_stream_bytes(&qb2).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(PATHED_MATERIAL_QUADLETS, group.qb64().unwrap());
     }
 
-    fn full_size(&self) -> ParsideResult<usize> {
-        let size = self.siger.full_size()?;
-        Ok(size)
+    #[test]
+    pub fn test_parse_pathed_material_quadlets_overrunning_count() {
+        let stream = PATHED_MATERIAL_QUADLETS.replacen("-LA7", "-LA6", 1);
+        let err = CesrGroup::from_stream_bytes(stream.as_bytes()).unwrap_err();
+        assert_eq!(
+            &ParsideError::QuadletCountMismatch { expected: 58, actual: 25 },
+            err.root_cause()
+        );
     }
 }
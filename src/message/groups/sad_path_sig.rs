@@ -1,16 +1,17 @@
 use crate::error::ParsideResult;
 use crate::message::cold_code::ColdCode;
+use crate::message::counter::{Codex, Counter};
 use crate::message::parsers::Parsers;
 use crate::message::{Group, GroupItem};
-use cesride::counter::Codex;
-use cesride::{Counter, Matter, Pather, Prefixer, Saider, Seqner};
-use nom::multi::count;
+use crate::utils::nom::{counted, field};
+use cesride::{Matter, Pather, Prefixer, Saider, Seqner};
 use nom::sequence::tuple;
+use serde::{Deserialize, Serialize};
 
 use super::{ControllerIdxSig, ControllerIdxSigs};
 
 // FIXME: Implement proper definition
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SadPathSigs {
     pub value: Vec<SadPathSig>,
 }
@@ -33,16 +34,17 @@ impl SadPathSigs {
         counter: &Counter,
         cold_code: &ColdCode,
     ) -> ParsideResult<(&'a [u8], SadPathSigs)> {
-        let (rest, body) = count(
+        let (rest, body) = counted(
             tuple((
-                Parsers::pather_parser(cold_code)?,
-                Parsers::counter_parser(cold_code)?,
-                Parsers::prefixer_parser(cold_code)?,
-                Parsers::seqner_parser(cold_code)?,
-                Parsers::saider_parser(cold_code)?,
-                Parsers::siger_list_parser(cold_code)?,
+                field("pather", Parsers::pather_parser(cold_code)?),
+                field("tcounter", Parsers::counter_parser(cold_code)?),
+                field("prefixer", Parsers::prefixer_parser(cold_code)?),
+                field("seqner", Parsers::seqner_parser(cold_code)?),
+                field("saider", Parsers::saider_parser(cold_code)?),
+                field("sigers", Parsers::siger_list_parser(cold_code, counter.counts_quadlets())?),
             )),
-            counter.count() as usize,
+            counter,
+            cold_code,
         )(bytes)?;
 
         let body = body
@@ -63,12 +65,17 @@ impl SadPathSigs {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SadPathSig {
+    #[serde(with = "crate::utils::serde::matter")]
     pub pather: Pather,
+    #[serde(with = "crate::utils::serde::counter")]
     pub tcounter: Counter,
+    #[serde(with = "crate::utils::serde::matter")]
     pub prefixer: Prefixer,
+    #[serde(with = "crate::utils::serde::matter")]
     pub seqner: Seqner,
+    #[serde(with = "crate::utils::serde::matter")]
     pub saider: Saider,
     pub sigers: ControllerIdxSigs,
 }
@@ -114,22 +121,22 @@ impl GroupItem for SadPathSig {
         let mut out = vec![0u8; self.full_size()? / 4 * 3];
         let mut offset = 0;
         let mut len = self.pather.full_size()? / 4 * 3;
-        out[offset..len].copy_from_slice(&self.pather.qb2()?);
+        out[offset..offset + len].copy_from_slice(&self.pather.qb2()?);
         offset += len;
         len = self.tcounter.full_size()? / 4 * 3;
         out[offset..offset + len].copy_from_slice(&self.tcounter.qb2()?);
         offset += len;
         len = self.prefixer.full_size()? / 4 * 3;
-        out[offset..len].copy_from_slice(&self.prefixer.qb2()?);
+        out[offset..offset + len].copy_from_slice(&self.prefixer.qb2()?);
         offset += len;
         len = self.seqner.full_size()? / 4 * 3;
-        out[offset..len].copy_from_slice(&self.seqner.qb2()?);
+        out[offset..offset + len].copy_from_slice(&self.seqner.qb2()?);
         offset += len;
         len = self.saider.full_size()? / 4 * 3;
-        out[offset..len].copy_from_slice(&self.saider.qb2()?);
+        out[offset..offset + len].copy_from_slice(&self.saider.qb2()?);
         offset += len;
         len = self.sigers.full_size()? / 4 * 3;
-        out[offset..len].copy_from_slice(&self.sigers.qb2()?);
+        out[offset..offset + len].copy_from_slice(&self.sigers.qb2()?);
         Ok(out)
     }
 
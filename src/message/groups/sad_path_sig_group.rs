@@ -1,12 +1,17 @@
-use crate::error::{ParsideError, ParsideResult};
+use crate::error::{ParsideError, ParsideResult, PathSegment};
 use crate::message::cold_code::ColdCode;
-use crate::message::{Group, GroupItem};
-use cesride::counter::Codex;
-use cesride::{Counter, Indexer, Siger};
+use crate::message::counter::{Codex, Counter};
+use crate::message::genus_version::GenusVersion;
+use crate::message::parsers::Parsers;
+use crate::message::{CesrGroup, Group, GroupItem, SadPathSigs};
+use crate::utils::nom::field;
+use cesride::{Matter, Pather};
+use serde::{Deserialize, Serialize};
 
-// FIXME: Implement proper definition
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SadPathSigGroups {
+    #[serde(with = "crate::utils::serde::matter")]
+    pub root: Pather,
     pub value: Vec<SadPathSigGroup>,
 }
 
@@ -14,50 +19,236 @@ impl Group<SadPathSigGroup> for SadPathSigGroups {
     const CODE: &'static str = Codex::SadPathSigGroup;
 
     fn new(value: Vec<SadPathSigGroup>) -> Self {
-        Self { value }
+        Self { root: Pather::default(), value }
     }
 
     fn value(&self) -> &Vec<SadPathSigGroup> {
         &self.value
     }
+
+    fn qb64(&self) -> ParsideResult<String> {
+        let mut out = self.counter()?.qb64()?;
+        out.push_str(&self.root.qb64()?);
+        for value in self.value().iter() {
+            out.push_str(&value.qb64()?);
+        }
+        Ok(out)
+    }
+
+    fn qb64b(&self) -> ParsideResult<Vec<u8>> {
+        let mut out = self.counter()?.qb64b()?;
+        out.extend_from_slice(&self.root.qb64b()?);
+        for value in self.value().iter() {
+            out.extend_from_slice(&value.qb64b()?);
+        }
+        Ok(out)
+    }
+
+    fn qb2(&self) -> ParsideResult<Vec<u8>> {
+        let mut out = self.counter()?.qb2()?;
+        out.extend_from_slice(&self.root.qb2()?);
+        for value in self.value().iter() {
+            out.extend_from_slice(&value.qb2()?);
+        }
+        Ok(out)
+    }
+
+    fn body_size(&self) -> ParsideResult<usize> {
+        let mut size = self.root.full_size()?;
+        for value in self.value().iter() {
+            size += value.full_size()?;
+        }
+        Ok(size)
+    }
 }
 
 impl SadPathSigGroups {
+    pub fn new_with_root(root: Pather, value: Vec<SadPathSigGroup>) -> Self {
+        Self { root, value }
+    }
+
     pub(crate) fn from_stream_bytes<'a>(
-        _bytes: &'a [u8],
-        _counter: &Counter,
-        _cold_code: &ColdCode,
+        bytes: &'a [u8],
+        counter: &Counter,
+        cold_code: &ColdCode,
+        depth: usize,
+        version: &GenusVersion,
     ) -> ParsideResult<(&'a [u8], SadPathSigGroups)> {
-        unimplemented!();
+        let (mut rest, root) = field("root", Parsers::pather_parser(cold_code)?)(bytes)?;
+
+        let mut body = vec![];
+        for index in 0..counter.count() as usize {
+            let (next, group) = SadPathSigGroup::from_stream_bytes(rest, cold_code, depth, version)
+                .map_err(|err| err.within(PathSegment::Index(index), rest))?;
+            body.push(group);
+            rest = next;
+        }
+
+        Ok((rest, SadPathSigGroups { root, value: body }))
     }
 }
 
-#[derive(Debug, Clone, Default)]
+/// Signatures of the content at a path relative to the root of the group
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SadPathSigGroup {
-    pub siger: Siger,
+    #[serde(with = "crate::utils::serde::matter")]
+    pub pather: Pather,
+    /// Either `TransIdxSigGroups` or `NonTransReceiptCouples`
+    pub sigs: CesrGroup,
 }
 
 impl SadPathSigGroup {
-    pub fn new(siger: Siger) -> Self {
-        Self { siger }
+    pub fn new(pather: Pather, sigs: CesrGroup) -> Self {
+        Self { pather, sigs }
+    }
+
+    fn counter() -> ParsideResult<Counter> {
+        Counter::new_with_code_and_count(SadPathSigs::CODE, 1)
+    }
+
+    fn from_stream_bytes<'a>(
+        bytes: &'a [u8],
+        cold_code: &ColdCode,
+        depth: usize,
+        version: &GenusVersion,
+    ) -> ParsideResult<(&'a [u8], SadPathSigGroup)> {
+        let (rest, counter) = Parsers::counter_parser(cold_code)?(bytes)?;
+        if counter.small_code() != SadPathSigs::CODE {
+            return Err(ParsideError::Unexpected(format!(
+                "Unexpected counter code {:?}, expected {:?}",
+                counter.code(),
+                SadPathSigs::CODE
+            )));
+        }
+        // a group item holds a single path and signature group
+        if counter.count() != 1 {
+            return Err(ParsideError::Unexpected(format!(
+                "Unexpected count {} of {:?}, expected 1",
+                counter.count(),
+                SadPathSigs::CODE
+            )));
+        }
+
+        let (rest, pather) = field("pather", Parsers::pather_parser(cold_code)?)(rest)?;
+        let (rest, sigs) = CesrGroup::from_nested_stream_bytes(rest, depth + 1, version)
+            .map_err(|err| err.within(PathSegment::Name("sigs"), rest))?;
+        match sigs {
+            CesrGroup::TransIdxSigGroupsVariant { .. }
+            | CesrGroup::NonTransReceiptCouplesVariant { .. } => {
+                Ok((rest, SadPathSigGroup { pather, sigs }))
+            }
+            _ => Err(ParsideError::Unexpected(
+                "Unexpected signature group in SadPathSigGroup".to_string(),
+            )),
+        }
     }
 }
 
 impl GroupItem for SadPathSigGroup {
     fn qb64(&self) -> ParsideResult<String> {
-        self.siger.qb64().map_err(ParsideError::from)
+        let mut out = Self::counter()?.qb64()?;
+        out += &self.pather.qb64()?;
+        out += &self.sigs.qb64()?;
+        Ok(out)
     }
 
     fn qb64b(&self) -> ParsideResult<Vec<u8>> {
-        self.siger.qb64b().map_err(ParsideError::from)
+        let mut out = Self::counter()?.qb64b()?;
+        out.extend_from_slice(&self.pather.qb64b()?);
+        out.extend_from_slice(&self.sigs.qb64b()?);
+        Ok(out)
     }
 
     fn qb2(&self) -> ParsideResult<Vec<u8>> {
-        self.siger.qb2().map_err(ParsideError::from)
+        let mut out = Self::counter()?.qb2()?;
+        out.extend_from_slice(&self.pather.qb2()?);
+        out.extend_from_slice(&self.sigs.qb2()?);
+        Ok(out)
     }
 
     fn full_size(&self) -> ParsideResult<usize> {
-        let size = self.siger.full_size()?;
+        let size =
+            Self::counter()?.full_size()? + self.pather.full_size()? + self.sigs.full_size()?;
         Ok(size)
     }
 }
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    pub use cesride::matter::Codex as MatterCodex;
+    use cesride::Bext;
+
+    const SAD_PATH_SIG_GROUPS: &str = concat!(
+        "-KAC6AABAAA-",
+        "-JAB5AABAA-a-FABEFhg5my9DuMU6gw1CVk6QgkmZKBttWSXDzVzWVmxh0_K0AAAAAAAAAAAAAAAAAAAAAAAEFhg5my9DuMU6gw1CVk6QgkmZKBttWSXDzVzWVmxh0_K-AABAADghKct9eYTuSgSd5wdPSYG06tGX7ZRp_BDnrgbSxJpsJtrA-fP7Pa1W602gHeMrO6HZsD1z3tWV5jGlApFmVIB",
+        "-JAB4AAB-a-b-CABBD8-gMSJ6K1PQ7_gG5ZJn2NkHQJgdkiNrTBz_FWWS_cC0BDc1i44ZX0jaIHh5oNDx-TITbPnI6VEn2nKlqPwkkTF452X7XxYh80tolDpReYwZpnD8TF4Or2v3CpSCikyt6EG"
+    );
+
+    #[test]
+    pub fn test_parse_sad_path_sig_groups() {
+        let (rest, group) = CesrGroup::from_stream_bytes(SAD_PATH_SIG_GROUPS.as_bytes()).unwrap();
+        assert!(rest.is_empty());
+        match group {
+            CesrGroup::SadPathSigGroupVariant { value: group } => {
+                assert_eq!("-", group.root.bext().unwrap());
+                assert_eq!(2, group.value.len());
+                assert_eq!("-a", group.value[0].pather.bext().unwrap());
+                match &group.value[0].sigs {
+                    CesrGroup::TransIdxSigGroupsVariant { value } => {
+                        assert_eq!(1, value.value.len());
+                        assert_eq!(
+                            MatterCodex::Blake3_256.to_string(),
+                            value.value[0].prefixer.code()
+                        );
+                    }
+                    _ => panic!("Unexpected case"),
+                }
+                assert_eq!("-a-b", group.value[1].pather.bext().unwrap());
+                match &group.value[1].sigs {
+                    CesrGroup::NonTransReceiptCouplesVariant { value } => {
+                        assert_eq!(1, value.value.len());
+                        assert_eq!(
+                            MatterCodex::Ed25519_Sig.to_string(),
+                            value.value[0].cigar.code()
+                        );
+                    }
+                    _ => panic!("Unexpected case"),
+                }
+            }
+            _ => panic!("Unexpected case"),
+        }
+    }
+
+    #[test]
+    pub fn test_sad_path_sig_groups_round_trip() {
+        let (_, group) = CesrGroup::from_stream_bytes(SAD_PATH_SIG_GROUPS.as_bytes()).unwrap();
+        assert_eq!(SAD_PATH_SIG_GROUPS, group.qb64().unwrap());
+        assert_eq!(SAD_PATH_SIG_GROUPS.as_bytes(), group.qb64b().unwrap());
+        assert_eq!(SAD_PATH_SIG_GROUPS.len(), group.full_size().unwrap());
+
+        let qb2 = group.qb2().unwrap();
+        assert_eq!(SAD_PATH_SIG_GROUPS.len() / 4 * 3, qb2.len());
+        let (rest, group) = CesrGroup::from_stream_bytes(&qb2).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(SAD_PATH_SIG_GROUPS, group.qb64().unwrap());
+    }
+
+    #[test]
+    pub fn test_parse_sad_path_sig_groups_with_unexpected_group() {
+        let stream = br#"-KAB6AABAAA--JAB5AABAA-a-AABAABg3q8uNg1A2jhEAdbKGf-QupQhNnmZQx3zIyPLWBe6qqLT5ynytivf9EwJhxyhy87a0x2cezDdil4SsM2xxs0O"#;
+        assert!(CesrGroup::from_stream_bytes(stream).is_err());
+    }
+
+    #[test]
+    pub fn test_parse_sad_path_sig_groups_with_unexpected_count() {
+        let stream = SAD_PATH_SIG_GROUPS.replacen("-JAB", "-JAC", 1);
+        match CesrGroup::from_stream_bytes(stream.as_bytes()).unwrap_err() {
+            ParsideError::Parse { path, source, .. } => {
+                assert_eq!("SadPathSigGroups[0]", path);
+                assert!(matches!(*source, ParsideError::Unexpected(..)));
+            }
+            err => panic!("Unexpected error {err:?}"),
+        }
+    }
+}
@@ -1,13 +1,14 @@
 use crate::error::ParsideResult;
 use crate::message::cold_code::ColdCode;
+use crate::message::counter::{Codex, Counter};
 use crate::message::parsers::Parsers;
 use crate::message::{Group, GroupItem};
-use cesride::counter::Codex;
-use cesride::{Counter, Matter, Saider, Seqner};
-use nom::multi::count;
+use crate::utils::nom::{counted, field};
+use cesride::{Matter, Saider, Seqner};
 use nom::sequence::tuple;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SealSourceCouples {
     pub value: Vec<SealSourceCouple>,
 }
@@ -30,9 +31,13 @@ impl SealSourceCouples {
         counter: &Counter,
         cold_code: &ColdCode,
     ) -> ParsideResult<(&'a [u8], SealSourceCouples)> {
-        let (rest, body) = count(
-            tuple((Parsers::seqner_parser(cold_code)?, Parsers::saider_parser(cold_code)?)),
-            counter.count() as usize,
+        let (rest, body) = counted(
+            tuple((
+                field("seqner", Parsers::seqner_parser(cold_code)?),
+                field("saider", Parsers::saider_parser(cold_code)?),
+            )),
+            counter,
+            cold_code,
         )(bytes)?;
         let body =
             body.into_iter().map(|(seqner, saider)| SealSourceCouple { seqner, saider }).collect();
@@ -41,9 +46,11 @@ impl SealSourceCouples {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SealSourceCouple {
+    #[serde(with = "crate::utils::serde::matter")]
     pub seqner: Seqner,
+    #[serde(with = "crate::utils::serde::matter")]
     pub saider: Saider,
 }
 
@@ -65,10 +72,10 @@ impl GroupItem for SealSourceCouple {
         let mut out = vec![0u8; self.full_size()?];
         let mut offset = 0;
         let mut len = self.seqner.full_size()?;
-        out[offset..len].copy_from_slice(&self.seqner.qb64b()?);
+        out[offset..offset + len].copy_from_slice(&self.seqner.qb64b()?);
         offset += len;
         len = self.saider.full_size()?;
-        out[offset..len].copy_from_slice(&self.saider.qb64b()?);
+        out[offset..offset + len].copy_from_slice(&self.saider.qb64b()?);
         Ok(out)
     }
 
@@ -76,10 +83,10 @@ impl GroupItem for SealSourceCouple {
         let mut out = vec![0u8; self.full_size()? / 4 * 3];
         let mut offset = 0;
         let mut len = self.seqner.full_size()? / 4 * 3;
-        out[offset..len].copy_from_slice(&self.seqner.qb2()?);
+        out[offset..offset + len].copy_from_slice(&self.seqner.qb2()?);
         offset += len;
         len = self.saider.full_size()? / 4 * 3;
-        out[offset..len].copy_from_slice(&self.saider.qb2()?);
+        out[offset..offset + len].copy_from_slice(&self.saider.qb2()?);
         Ok(out)
     }
 
@@ -0,0 +1,156 @@
+use crate::error::ParsideResult;
+use crate::message::cold_code::ColdCode;
+use crate::message::counter::{Codex, Counter};
+use crate::message::parsers::Parsers;
+use crate::message::{Group, GroupItem};
+use crate::utils::nom::{counted, field};
+use cesride::{Matter, Prefixer, Saider, Seqner};
+use nom::sequence::tuple;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SealSourceTriples {
+    pub value: Vec<SealSourceTriple>,
+}
+
+impl Group<SealSourceTriple> for SealSourceTriples {
+    const CODE: &'static str = Codex::SealSourceTriples;
+
+    fn new(value: Vec<SealSourceTriple>) -> Self {
+        Self { value }
+    }
+
+    fn value(&self) -> &Vec<SealSourceTriple> {
+        &self.value
+    }
+}
+
+impl SealSourceTriples {
+    pub(crate) fn from_stream_bytes<'a>(
+        bytes: &'a [u8],
+        counter: &Counter,
+        cold_code: &ColdCode,
+    ) -> ParsideResult<(&'a [u8], SealSourceTriples)> {
+        let (rest, body) = counted(
+            tuple((
+                field("prefixer", Parsers::prefixer_parser(cold_code)?),
+                field("seqner", Parsers::seqner_parser(cold_code)?),
+                field("saider", Parsers::saider_parser(cold_code)?),
+            )),
+            counter,
+            cold_code,
+        )(bytes)?;
+        let body = body
+            .into_iter()
+            .map(|(prefixer, seqner, saider)| SealSourceTriple { prefixer, seqner, saider })
+            .collect();
+
+        Ok((rest, SealSourceTriples { value: body }))
+    }
+}
+
+/// Anchoring source event of another controller's KEL, e.g. issuer of a TEL event
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SealSourceTriple {
+    #[serde(with = "crate::utils::serde::matter")]
+    pub prefixer: Prefixer,
+    #[serde(with = "crate::utils::serde::matter")]
+    pub seqner: Seqner,
+    #[serde(with = "crate::utils::serde::matter")]
+    pub saider: Saider,
+}
+
+impl SealSourceTriple {
+    pub fn new(prefixer: Prefixer, seqner: Seqner, saider: Saider) -> Self {
+        Self { prefixer, seqner, saider }
+    }
+}
+
+impl GroupItem for SealSourceTriple {
+    fn qb64(&self) -> ParsideResult<String> {
+        let mut out = "\0".repeat(self.full_size()?);
+        let mut offset = 0;
+        let mut len = self.prefixer.full_size()?;
+        unsafe { out[offset..offset + len].as_bytes_mut() }
+            .copy_from_slice(self.prefixer.qb64()?.as_bytes());
+        offset += len;
+        len = self.seqner.full_size()?;
+        unsafe { out[offset..offset + len].as_bytes_mut() }
+            .copy_from_slice(self.seqner.qb64()?.as_bytes());
+        offset += len;
+        len = self.saider.full_size()?;
+        unsafe { out[offset..offset + len].as_bytes_mut() }
+            .copy_from_slice(self.saider.qb64()?.as_bytes());
+        Ok(out)
+    }
+
+    fn qb64b(&self) -> ParsideResult<Vec<u8>> {
+        let mut out = vec![0u8; self.full_size()?];
+        let mut offset = 0;
+        let mut len = self.prefixer.full_size()?;
+        out[offset..offset + len].copy_from_slice(&self.prefixer.qb64b()?);
+        offset += len;
+        len = self.seqner.full_size()?;
+        out[offset..offset + len].copy_from_slice(&self.seqner.qb64b()?);
+        offset += len;
+        len = self.saider.full_size()?;
+        out[offset..offset + len].copy_from_slice(&self.saider.qb64b()?);
+        Ok(out)
+    }
+
+    fn qb2(&self) -> ParsideResult<Vec<u8>> {
+        let mut out = vec![0u8; self.full_size()? / 4 * 3];
+        let mut offset = 0;
+        let mut len = self.prefixer.full_size()? / 4 * 3;
+        out[offset..offset + len].copy_from_slice(&self.prefixer.qb2()?);
+        offset += len;
+        len = self.seqner.full_size()? / 4 * 3;
+        out[offset..offset + len].copy_from_slice(&self.seqner.qb2()?);
+        offset += len;
+        len = self.saider.full_size()? / 4 * 3;
+        out[offset..offset + len].copy_from_slice(&self.saider.qb2()?);
+        Ok(out)
+    }
+
+    fn full_size(&self) -> ParsideResult<usize> {
+        let size =
+            self.prefixer.full_size()? + self.seqner.full_size()? + self.saider.full_size()?;
+        Ok(size)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::CesrGroup;
+
+    const SEAL_SOURCE_TRIPLES: &str = concat!(
+        "-IAB",
+        "EFhg5my9DuMU6gw1CVk6QgkmZKBttWSXDzVzWVmxh0_K",
+        "0AAAAAAAAAAAAAAAAAAAAAAB",
+        "EFhg5my9DuMU6gw1CVk6QgkmZKBttWSXDzVzWVmxh0_K"
+    );
+
+    #[test]
+    pub fn test_parse_seal_source_triples() {
+        let (rest, group) = CesrGroup::from_stream_bytes(SEAL_SOURCE_TRIPLES.as_bytes()).unwrap();
+        assert!(rest.is_empty());
+        match &group {
+            CesrGroup::SealSourceTriplesVariant { value } => {
+                assert_eq!(1, value.value.len());
+                assert_eq!(1, value.value[0].seqner.sn().unwrap());
+                assert_eq!(
+                    "EFhg5my9DuMU6gw1CVk6QgkmZKBttWSXDzVzWVmxh0_K",
+                    value.value[0].prefixer.qb64().unwrap()
+                );
+            }
+            _ => panic!("Unexpected case"),
+        }
+
+        assert_eq!(SEAL_SOURCE_TRIPLES, group.qb64().unwrap());
+        let qb2 = group.qb2().unwrap();
+        let (rest, group) = CesrGroup::from_stream_bytes(&qb2).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(SEAL_SOURCE_TRIPLES, group.qb64().unwrap());
+    }
+}
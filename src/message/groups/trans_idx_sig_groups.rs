@@ -1,15 +1,16 @@
 use crate::error::ParsideResult;
 use crate::message::cold_code::ColdCode;
 use crate::message::controller_idx_sigs::ControllerIdxSig;
+use crate::message::counter::{Codex, Counter};
 use crate::message::groups::controller_idx_sigs::ControllerIdxSigs;
 use crate::message::parsers::Parsers;
 use crate::message::{Group, GroupItem};
-use cesride::counter::Codex;
-use cesride::{Counter, Matter, Prefixer, Saider, Seqner};
-use nom::multi::count;
+use crate::utils::nom::{counted, field};
+use cesride::{Matter, Prefixer, Saider, Seqner};
 use nom::sequence::tuple;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TransIdxSigGroups {
     pub value: Vec<TransIdxSigGroup>,
 }
@@ -32,14 +33,15 @@ impl TransIdxSigGroups {
         counter: &Counter,
         cold_code: &ColdCode,
     ) -> ParsideResult<(&'a [u8], TransIdxSigGroups)> {
-        let (rest, body) = count(
+        let (rest, body) = counted(
             tuple((
-                Parsers::prefixer_parser(cold_code)?,
-                Parsers::seqner_parser(cold_code)?,
-                Parsers::saider_parser(cold_code)?,
-                Parsers::siger_list_parser(cold_code)?,
+                field("prefixer", Parsers::prefixer_parser(cold_code)?),
+                field("seqner", Parsers::seqner_parser(cold_code)?),
+                field("saider", Parsers::saider_parser(cold_code)?),
+                field("isigers", Parsers::siger_list_parser(cold_code, counter.counts_quadlets())?),
             )),
-            counter.count() as usize,
+            counter,
+            cold_code,
         )(bytes)?;
 
         let body = body
@@ -58,10 +60,13 @@ impl TransIdxSigGroups {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TransIdxSigGroup {
+    #[serde(with = "crate::utils::serde::matter")]
     pub prefixer: Prefixer,
+    #[serde(with = "crate::utils::serde::matter")]
     pub seqner: Seqner,
+    #[serde(with = "crate::utils::serde::matter")]
     pub saider: Saider,
     pub isigers: ControllerIdxSigs,
 }
@@ -75,87 +80,66 @@ impl TransIdxSigGroup {
     ) -> Self {
         Self { prefixer, seqner, saider, isigers }
     }
-
-    fn counter() -> ParsideResult<Counter> {
-        Ok(Counter::new_with_code_and_count(Codex::TransIdxSigGroups, 1)?)
-    }
 }
 
 impl GroupItem for TransIdxSigGroup {
     fn qb64(&self) -> ParsideResult<String> {
-        let counter = Self::counter()?;
-
         let mut out = "\0".repeat(self.full_size()?);
-        let mut start = 0;
-        let mut end = counter.full_size()?;
-
-        unsafe { out[start..end].as_bytes_mut() }.copy_from_slice(counter.qb64()?.as_bytes());
-        start = end;
-        end += self.prefixer.full_size()?;
-        unsafe { out[start..end].as_bytes_mut() }.copy_from_slice(self.prefixer.qb64()?.as_bytes());
-        start = end;
-        end += self.seqner.full_size()?;
-        unsafe { out[start..end].as_bytes_mut() }.copy_from_slice(self.seqner.qb64()?.as_bytes());
-        start = end;
-        end += self.saider.full_size()?;
-        unsafe { out[start..end].as_bytes_mut() }.copy_from_slice(self.saider.qb64()?.as_bytes());
-        start = end;
-        end += self.isigers.full_size()?;
-        unsafe { out[start..end].as_bytes_mut() }.copy_from_slice(self.isigers.qb64()?.as_bytes());
-
+        let mut offset = 0;
+        let mut len = self.prefixer.full_size()?;
+        unsafe { out[offset..offset + len].as_bytes_mut() }
+            .copy_from_slice(self.prefixer.qb64()?.as_bytes());
+        offset += len;
+        len = self.seqner.full_size()?;
+        unsafe { out[offset..offset + len].as_bytes_mut() }
+            .copy_from_slice(self.seqner.qb64()?.as_bytes());
+        offset += len;
+        len = self.saider.full_size()?;
+        unsafe { out[offset..offset + len].as_bytes_mut() }
+            .copy_from_slice(self.saider.qb64()?.as_bytes());
+        offset += len;
+        len = self.isigers.full_size()?;
+        unsafe { out[offset..offset + len].as_bytes_mut() }
+            .copy_from_slice(self.isigers.qb64()?.as_bytes());
         Ok(out)
     }
 
     fn qb64b(&self) -> ParsideResult<Vec<u8>> {
-        let counter = Self::counter()?;
-
         let mut out = vec![0u8; self.full_size()?];
-        let mut start = 0;
-        let mut end = counter.full_size()?;
-
-        out[start..end].copy_from_slice(&counter.qb64b()?);
-        start = end;
-        end += self.prefixer.full_size()?;
-        out[start..end].copy_from_slice(&self.prefixer.qb64b()?);
-        start = end;
-        end += self.seqner.full_size()?;
-        out[start..end].copy_from_slice(&self.seqner.qb64b()?);
-        start = end;
-        end += self.saider.full_size()?;
-        out[start..end].copy_from_slice(&self.saider.qb64b()?);
-        start = end;
-        end += self.isigers.full_size()?;
-        out[start..end].copy_from_slice(&self.isigers.qb64b()?);
-
+        let mut offset = 0;
+        let mut len = self.prefixer.full_size()?;
+        out[offset..offset + len].copy_from_slice(&self.prefixer.qb64b()?);
+        offset += len;
+        len = self.seqner.full_size()?;
+        out[offset..offset + len].copy_from_slice(&self.seqner.qb64b()?);
+        offset += len;
+        len = self.saider.full_size()?;
+        out[offset..offset + len].copy_from_slice(&self.saider.qb64b()?);
+        offset += len;
+        len = self.isigers.full_size()?;
+        out[offset..offset + len].copy_from_slice(&self.isigers.qb64b()?);
         Ok(out)
     }
 
     fn qb2(&self) -> ParsideResult<Vec<u8>> {
-        let counter = Self::counter()?;
-
         let mut out = vec![0u8; self.full_size()? / 4 * 3];
-        let mut start = 0;
-        let mut end = counter.full_size()?;
-
-        out[start..end].copy_from_slice(&counter.qb2()?);
-        start = end;
-        end += self.prefixer.full_size()? / 4 * 3;
-        out[start..end].copy_from_slice(&self.prefixer.qb2()?);
-        start = end;
-        end += self.seqner.full_size()? / 4 * 3;
-        out[start..end].copy_from_slice(&self.seqner.qb2()?);
-        start = end;
-        end += self.saider.full_size()? / 4 * 3;
-        out[start..end].copy_from_slice(&self.saider.qb2()?);
-        start = end;
-        end += self.isigers.full_size()? / 4 * 3;
-        out[start..end].copy_from_slice(&self.isigers.qb2()?);
+        let mut offset = 0;
+        let mut len = self.prefixer.full_size()? / 4 * 3;
+        out[offset..offset + len].copy_from_slice(&self.prefixer.qb2()?);
+        offset += len;
+        len = self.seqner.full_size()? / 4 * 3;
+        out[offset..offset + len].copy_from_slice(&self.seqner.qb2()?);
+        offset += len;
+        len = self.saider.full_size()? / 4 * 3;
+        out[offset..offset + len].copy_from_slice(&self.saider.qb2()?);
+        offset += len;
+        len = self.isigers.full_size()? / 4 * 3;
+        out[offset..offset + len].copy_from_slice(&self.isigers.qb2()?);
         Ok(out)
     }
 
     fn full_size(&self) -> ParsideResult<usize> {
-        let size = Self::counter()?.full_size()?
-            + self.prefixer.full_size()?
+        let size = self.prefixer.full_size()?
             + self.seqner.full_size()?
             + self.saider.full_size()?
             + self.isigers.full_size()?;
@@ -172,8 +156,7 @@ pub mod tests {
     pub fn test_parse_trans_idx_sig_groups() {
         let stream = br#"EFhg5my9DuMU6gw1CVk6QgkmZKBttWSXDzVzWVmxh0_K0AAAAAAAAAAAAAAAAAAAAAAAEFhg5my9DuMU6gw1CVk6QgkmZKBttWSXDzVzWVmxh0_K-AABAADghKct9eYTuSgSd5wdPSYG06tGX7ZRp_BDnrgbSxJpsJtrA-fP7Pa1W602gHeMrO6HZsD1z3tWV5jGlApFmVIB"#;
 
-        let counter =
-            Counter::new(Some(1), None, Some(TransIdxSigGroups::CODE), None, None, None).unwrap();
+        let counter = Counter::new_with_code_and_count(TransIdxSigGroups::CODE, 1).unwrap();
         let (rest, group) =
             TransIdxSigGroups::from_stream_bytes(stream, &counter, &ColdCode::CtB64).unwrap();
         assert!(rest.is_empty());
@@ -182,4 +165,25 @@ pub mod tests {
         assert_eq!(MatterCodex::Salt_128.to_string(), group.value[0].seqner.code());
         assert_eq!(MatterCodex::Blake3_256.to_string(), group.value[0].saider.code());
     }
+
+    #[test]
+    pub fn test_encode_trans_idx_sig_groups() {
+        let stream = br#"EFhg5my9DuMU6gw1CVk6QgkmZKBttWSXDzVzWVmxh0_K0AAAAAAAAAAAAAAAAAAAAAAAEFhg5my9DuMU6gw1CVk6QgkmZKBttWSXDzVzWVmxh0_K-AABAADghKct9eYTuSgSd5wdPSYG06tGX7ZRp_BDnrgbSxJpsJtrA-fP7Pa1W602gHeMrO6HZsD1z3tWV5jGlApFmVIB"#;
+
+        // items are encoded without a counter of their own
+        let counter = Counter::new_with_code_and_count(TransIdxSigGroups::CODE, 1).unwrap();
+        let (_, group) =
+            TransIdxSigGroups::from_stream_bytes(stream, &counter, &ColdCode::CtB64).unwrap();
+        let item = &group.value[0];
+        assert_eq!(stream.len(), item.full_size().unwrap());
+        assert_eq!(stream.to_vec(), item.qb64b().unwrap());
+        assert_eq!(stream.to_vec(), item.qb64().unwrap().into_bytes());
+
+        let qb2 = item.qb2().unwrap();
+        assert_eq!(stream.len() / 4 * 3, qb2.len());
+        let (rest, group) =
+            TransIdxSigGroups::from_stream_bytes(&qb2, &counter, &ColdCode::CtOpB2).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(stream.to_vec(), group.value[0].qb64b().unwrap());
+    }
 }
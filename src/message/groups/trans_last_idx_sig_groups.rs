@@ -1,15 +1,16 @@
 use crate::error::ParsideResult;
 use crate::message::cold_code::ColdCode;
 use crate::message::controller_idx_sigs::ControllerIdxSig;
+use crate::message::counter::{Codex, Counter};
 use crate::message::groups::controller_idx_sigs::ControllerIdxSigs;
 use crate::message::parsers::Parsers;
 use crate::message::{Group, GroupItem};
-use cesride::counter::Codex;
-use cesride::{Counter, Matter, Prefixer};
-use nom::multi::count;
+use crate::utils::nom::{counted, field};
+use cesride::{Matter, Prefixer};
 use nom::sequence::tuple;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TransLastIdxSigGroups {
     pub value: Vec<TransLastIdxSigGroup>,
 }
@@ -32,9 +33,13 @@ impl TransLastIdxSigGroups {
         counter: &Counter,
         cold_code: &ColdCode,
     ) -> ParsideResult<(&'a [u8], TransLastIdxSigGroups)> {
-        let (rest, body) = count(
-            tuple((Parsers::prefixer_parser(cold_code)?, Parsers::siger_list_parser(cold_code)?)),
-            counter.count() as usize,
+        let (rest, body) = counted(
+            tuple((
+                field("prefixer", Parsers::prefixer_parser(cold_code)?),
+                field("isigers", Parsers::siger_list_parser(cold_code, counter.counts_quadlets())?),
+            )),
+            counter,
+            cold_code,
         )(bytes)?;
 
         let body = body
@@ -51,8 +56,9 @@ impl TransLastIdxSigGroups {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TransLastIdxSigGroup {
+    #[serde(with = "crate::utils::serde::matter")]
     pub prefixer: Prefixer,
     pub isigers: ControllerIdxSigs,
 }
@@ -68,11 +74,12 @@ impl GroupItem for TransLastIdxSigGroup {
         let mut out = "\0".repeat(self.full_size()?);
         let mut offset = 0;
         let mut len = self.prefixer.full_size()?;
-        unsafe { out[offset..len].as_bytes_mut() }
+        unsafe { out[offset..offset + len].as_bytes_mut() }
             .copy_from_slice(self.prefixer.qb64()?.as_bytes());
         offset += len;
         len = self.isigers.full_size()?;
-        unsafe { out[offset..len].as_bytes_mut() }.copy_from_slice(self.isigers.qb64()?.as_bytes());
+        unsafe { out[offset..offset + len].as_bytes_mut() }
+            .copy_from_slice(self.isigers.qb64()?.as_bytes());
         Ok(out)
     }
 
@@ -80,10 +87,10 @@ impl GroupItem for TransLastIdxSigGroup {
         let mut out = vec![0u8; self.full_size()?];
         let mut offset = 0;
         let mut len = self.prefixer.full_size()?;
-        out[offset..len].copy_from_slice(&self.prefixer.qb64b()?);
+        out[offset..offset + len].copy_from_slice(&self.prefixer.qb64b()?);
         offset += len;
         len = self.isigers.full_size()?;
-        out[offset..len].copy_from_slice(&self.isigers.qb64b()?);
+        out[offset..offset + len].copy_from_slice(&self.isigers.qb64b()?);
         Ok(out)
     }
 
@@ -91,10 +98,10 @@ impl GroupItem for TransLastIdxSigGroup {
         let mut out = vec![0u8; self.full_size()? / 4 * 3];
         let mut offset = 0;
         let mut len = self.prefixer.full_size()? / 4 * 3;
-        out[offset..len].copy_from_slice(&self.prefixer.qb2()?);
+        out[offset..offset + len].copy_from_slice(&self.prefixer.qb2()?);
         offset += len;
         len = self.isigers.full_size()? / 4 * 3;
-        out[offset..len].copy_from_slice(&self.isigers.qb2()?);
+        out[offset..offset + len].copy_from_slice(&self.isigers.qb2()?);
         Ok(out)
     }
 
@@ -113,9 +120,7 @@ pub mod tests {
     pub fn test_parse_trans_last_idx_sig_groups() {
         let stream = br#"EB1f36VmoizOIpBIBv3X4ZiWJQWjtKJ7TMmsZltT0B32-AABAAAKB9u6wyLS9kl_iGVGCqrs-3XqFbyGeOKuiOEA9JZpxI9GMv0GJv2wbY1-sOD_HOJcvXO7LSO8g8MSeRXjtL4I"#;
 
-        let counter =
-            Counter::new(Some(1), None, Some(TransLastIdxSigGroups::CODE), None, None, None)
-                .unwrap();
+        let counter = Counter::new_with_code_and_count(TransLastIdxSigGroups::CODE, 1).unwrap();
         let (rest, group) =
             TransLastIdxSigGroups::from_stream_bytes(stream, &counter, &ColdCode::CtB64).unwrap();
         assert!(rest.is_empty());
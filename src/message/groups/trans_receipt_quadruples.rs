@@ -1,13 +1,14 @@
 use crate::error::ParsideResult;
 use crate::message::cold_code::ColdCode;
+use crate::message::counter::{Codex, Counter};
 use crate::message::parsers::Parsers;
 use crate::message::{Group, GroupItem};
-use cesride::counter::Codex;
-use cesride::{Counter, Indexer, Matter, Prefixer, Saider, Seqner, Siger};
-use nom::multi::count;
+use crate::utils::nom::{counted, field};
+use cesride::{Indexer, Matter, Prefixer, Saider, Seqner, Siger};
 use nom::sequence::tuple;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TransReceiptQuadruples {
     pub value: Vec<TransReceiptQuadruple>,
 }
@@ -30,14 +31,15 @@ impl TransReceiptQuadruples {
         counter: &Counter,
         cold_code: &ColdCode,
     ) -> ParsideResult<(&'a [u8], TransReceiptQuadruples)> {
-        let (rest, body) = count(
+        let (rest, body) = counted(
             tuple((
-                Parsers::prefixer_parser(cold_code)?,
-                Parsers::seqner_parser(cold_code)?,
-                Parsers::saider_parser(cold_code)?,
-                Parsers::siger_parser(cold_code)?,
+                field("prefixer", Parsers::prefixer_parser(cold_code)?),
+                field("seqner", Parsers::seqner_parser(cold_code)?),
+                field("saider", Parsers::saider_parser(cold_code)?),
+                field("siger", Parsers::siger_parser(cold_code)?),
             )),
-            counter.count() as usize,
+            counter,
+            cold_code,
         )(bytes)?;
         let body = body
             .into_iter()
@@ -53,11 +55,15 @@ impl TransReceiptQuadruples {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TransReceiptQuadruple {
+    #[serde(with = "crate::utils::serde::matter")]
     pub prefixer: Prefixer,
+    #[serde(with = "crate::utils::serde::matter")]
     pub seqner: Seqner,
+    #[serde(with = "crate::utils::serde::matter")]
     pub saider: Saider,
+    #[serde(with = "crate::utils::serde::indexer")]
     pub siger: Siger,
 }
 
@@ -72,17 +78,20 @@ impl GroupItem for TransReceiptQuadruple {
         let mut out = "\0".repeat(self.full_size()?);
         let mut offset = 0;
         let mut len = self.prefixer.full_size()?;
-        unsafe { out[offset..len].as_bytes_mut() }
+        unsafe { out[offset..offset + len].as_bytes_mut() }
             .copy_from_slice(self.prefixer.qb64()?.as_bytes());
         offset += len;
         len = self.seqner.full_size()?;
-        unsafe { out[offset..len].as_bytes_mut() }.copy_from_slice(self.seqner.qb64()?.as_bytes());
+        unsafe { out[offset..offset + len].as_bytes_mut() }
+            .copy_from_slice(self.seqner.qb64()?.as_bytes());
         offset += len;
         len = self.saider.full_size()?;
-        unsafe { out[offset..len].as_bytes_mut() }.copy_from_slice(self.saider.qb64()?.as_bytes());
+        unsafe { out[offset..offset + len].as_bytes_mut() }
+            .copy_from_slice(self.saider.qb64()?.as_bytes());
         offset += len;
         len = self.siger.full_size()?;
-        unsafe { out[offset..len].as_bytes_mut() }.copy_from_slice(self.siger.qb64()?.as_bytes());
+        unsafe { out[offset..offset + len].as_bytes_mut() }
+            .copy_from_slice(self.siger.qb64()?.as_bytes());
         Ok(out)
     }
 
@@ -90,16 +99,16 @@ impl GroupItem for TransReceiptQuadruple {
         let mut out = vec![0u8; self.full_size()?];
         let mut offset = 0;
         let mut len = self.prefixer.full_size()?;
-        out[offset..len].copy_from_slice(&self.prefixer.qb64b()?);
+        out[offset..offset + len].copy_from_slice(&self.prefixer.qb64b()?);
         offset += len;
         len = self.seqner.full_size()?;
-        out[offset..len].copy_from_slice(&self.seqner.qb64b()?);
+        out[offset..offset + len].copy_from_slice(&self.seqner.qb64b()?);
         offset += len;
         len = self.saider.full_size()?;
-        out[offset..len].copy_from_slice(&self.saider.qb64b()?);
+        out[offset..offset + len].copy_from_slice(&self.saider.qb64b()?);
         offset += len;
         len = self.siger.full_size()?;
-        out[offset..len].copy_from_slice(&self.siger.qb64b()?);
+        out[offset..offset + len].copy_from_slice(&self.siger.qb64b()?);
         Ok(out)
     }
 
@@ -107,16 +116,16 @@ impl GroupItem for TransReceiptQuadruple {
         let mut out = vec![0u8; self.full_size()? / 4 * 3];
         let mut offset = 0;
         let mut len = self.prefixer.full_size()? / 4 * 3;
-        out[offset..len].copy_from_slice(&self.prefixer.qb2()?);
+        out[offset..offset + len].copy_from_slice(&self.prefixer.qb2()?);
         offset += len;
         len = self.seqner.full_size()? / 4 * 3;
-        out[offset..len].copy_from_slice(&self.seqner.qb2()?);
+        out[offset..offset + len].copy_from_slice(&self.seqner.qb2()?);
         offset += len;
         len = self.saider.full_size()? / 4 * 3;
-        out[offset..len].copy_from_slice(&self.saider.qb2()?);
+        out[offset..offset + len].copy_from_slice(&self.saider.qb2()?);
         offset += len;
         len = self.siger.full_size()? / 4 * 3;
-        out[offset..len].copy_from_slice(&self.siger.qb2()?);
+        out[offset..offset + len].copy_from_slice(&self.siger.qb2()?);
         Ok(out)
     }
 
@@ -1,12 +1,13 @@
 use crate::error::{ParsideError, ParsideResult};
 use crate::message::cold_code::ColdCode;
+use crate::message::counter::{Codex, Counter};
 use crate::message::parsers::Parsers;
 use crate::message::{Group, GroupItem};
-use cesride::counter::Codex;
-use cesride::{Counter, Indexer, Siger};
-use nom::multi::count;
+use crate::utils::nom::counted;
+use cesride::{Indexer, Siger};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct WitnessIdxSigs {
     pub value: Vec<WitnessIdxSig>,
 }
@@ -29,15 +30,15 @@ impl WitnessIdxSigs {
         counter: &Counter,
         cold_code: &ColdCode,
     ) -> ParsideResult<(&'a [u8], WitnessIdxSigs)> {
-        let (rest, body) =
-            count(Parsers::siger_parser(cold_code)?, counter.count() as usize)(bytes)?;
+        let (rest, body) = counted(Parsers::siger_parser(cold_code)?, counter, cold_code)(bytes)?;
         let body = body.into_iter().map(|siger| WitnessIdxSig { siger }).collect();
         Ok((rest, WitnessIdxSigs { value: body }))
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct WitnessIdxSig {
+    #[serde(with = "crate::utils::serde::indexer")]
     pub siger: Siger,
 }
 
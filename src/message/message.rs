@@ -1,63 +1,213 @@
+use std::io::Write;
+
 use serde::de::DeserializeOwned;
+use serde::Serialize;
 
 use crate::error::{ParsideError, ParsideResult};
 use crate::message::cold_code::ColdCode;
-use crate::message::custom_payload::CustomPayload;
+use crate::message::counter::Counter;
+use crate::message::custom_payload::{CustomPayload, Framing};
+use crate::message::domain::Domain;
+use crate::message::genus_version::GenusVersion;
 use crate::message::groups::CesrGroup;
+use crate::message::native_body::NativeBody;
+use crate::message::parsers::Parsers;
 
-/// Datastructures representing single parsed message which either custom payload or known CESR group
-#[derive(Debug)]
+/// Datastructures representing single parsed message which either custom payload, CESR 2.0 native
+/// body or known CESR group. `version` is the genus/version of the code tables active where the
+/// message was parsed, `counters` are the genus/version counters read right before the message.
+/// Match variants with `..`, more variants and fields may follow.
+#[derive(Debug, Serialize)]
+#[non_exhaustive]
 pub enum Message {
-    Custom { value: CustomPayload },
-    Group { value: CesrGroup },
+    #[non_exhaustive]
+    Custom {
+        value: CustomPayload,
+        version: GenusVersion,
+        #[serde(skip)]
+        counters: Vec<Counter>,
+    },
+    #[non_exhaustive]
+    Native {
+        value: NativeBody,
+        version: GenusVersion,
+        #[serde(skip)]
+        counters: Vec<Counter>,
+    },
+    #[non_exhaustive]
+    Group {
+        value: CesrGroup,
+        version: GenusVersion,
+        #[serde(skip)]
+        counters: Vec<Counter>,
+    },
 }
 
 impl Message {
     /// Parse single message from provided bytes
     pub fn from_stream_bytes(bytes: &[u8]) -> ParsideResult<(&[u8], Message)> {
+        Self::from_stream_bytes_with_framing(bytes, Framing::default())
+    }
+
+    /// Parse single message from provided bytes, finding the end of custom payload with `framing`
+    pub fn from_stream_bytes_with_framing(
+        bytes: &[u8],
+        framing: Framing,
+    ) -> ParsideResult<(&[u8], Message)> {
+        Self::from_stream_bytes_with_version(bytes, framing, &GenusVersion::default())
+    }
+
+    /// Parse single message from provided bytes with the code tables of `version` active.
+    /// Genus/version counters preceding the message switch the tables the message is parsed with,
+    /// the tables of the returned message stay active for the rest of the stream.
+    pub fn from_stream_bytes_with_version<'a>(
+        bytes: &'a [u8],
+        framing: Framing,
+        version: &GenusVersion,
+    ) -> ParsideResult<(&'a [u8], Message)> {
         if bytes.is_empty() {
             return Err(ParsideError::EmptyBytesStream);
         }
 
-        let cold_code = ColdCode::try_from(bytes[0])?;
+        let (rest, counters, version) = Self::genus_version(bytes, version)?;
+        if rest.is_empty() {
+            // genus/version counter applies to the message still to come
+            return Err(ParsideError::Incomplete(1));
+        }
+
+        let cold_code = ColdCode::try_from(rest[0])?;
         match cold_code {
+            ColdCode::CtB64 | ColdCode::CtOpB2 if Self::is_native_body(rest, &version) => {
+                NativeBody::from_stream_bytes(rest)
+                    .map(|(rest, value)| (rest, Message::Native { value, version, counters }))
+                    .map_err(|err| err.shifted(bytes.len() - rest.len()))
+            }
             ColdCode::CtB64 | ColdCode::CtOpB2 | ColdCode::OpB64 => {
-                CesrGroup::from_stream_bytes(bytes)
-                    .map(|(rest, value)| (rest, Message::Group { value }))
+                CesrGroup::from_nested_stream_bytes(rest, 0, &version)
+                    .map(|(rest, value)| (rest, Message::Group { value, version, counters }))
+                    .map_err(|err| err.located(bytes.len()))
             }
-            ColdCode::Json => CustomPayload::from_json_stream(bytes)
-                .map(|(rest, value)| (rest, Message::Custom { value })),
-            ColdCode::Cbor => CustomPayload::from_cbor_stream(bytes)
-                .map(|(rest, value)| (rest, Message::Custom { value })),
-            ColdCode::MGPK1 | ColdCode::MGPK2 => CustomPayload::from_mgpk_stream(bytes)
-                .map(|(rest, value)| (rest, Message::Custom { value })),
+            ColdCode::Json => CustomPayload::from_json_stream(rest, framing)
+                .map(|(rest, value)| (rest, Message::Custom { value, version, counters })),
+            ColdCode::Cbor => CustomPayload::from_cbor_stream(rest, framing)
+                .map(|(rest, value)| (rest, Message::Custom { value, version, counters })),
+            ColdCode::MGPK1 | ColdCode::MGPK2 => CustomPayload::from_mgpk_stream(rest, framing)
+                .map(|(rest, value)| (rest, Message::Custom { value, version, counters })),
             ColdCode::Free => {
-                Err(ParsideError::Unexpected(format!("Unsupported cold code {}", bytes[0])))
+                Err(ParsideError::Unexpected(format!("Unsupported cold code {}", rest[0])))
             }
         }
     }
 
+    /// Whether bytes start with a native body group, known to CESR 2.0 tables only
+    fn is_native_body(bytes: &[u8], version: &GenusVersion) -> bool {
+        if !version.counts_quadlets() {
+            return false;
+        }
+        let counter = ColdCode::try_from(bytes[0])
+            .and_then(|cold_code| Parsers::counter_parser(&cold_code))
+            .ok()
+            .and_then(|parser| parser(bytes).ok());
+        counter.is_some_and(|(_, counter)| NativeBody::has_code(&counter.small_code()))
+    }
+
+    /// Read genus/version counters at the beginning of bytes, returning them with the version
+    /// they select
+    fn genus_version<'a>(
+        mut bytes: &'a [u8],
+        version: &GenusVersion,
+    ) -> ParsideResult<(&'a [u8], Vec<Counter>, GenusVersion)> {
+        let mut version = version.clone();
+        let mut counters = vec![];
+        while let Some(cold_code @ (ColdCode::CtB64 | ColdCode::CtOpB2)) =
+            bytes.first().and_then(|byte| ColdCode::try_from(*byte).ok())
+        {
+            // anything but a complete genus/version counter is left for the group parser
+            let Ok((rest, counter)) = Parsers::counter_parser(&cold_code)?(bytes) else {
+                break;
+            };
+            match GenusVersion::from_counter(&counter)? {
+                Some(selected) => version = selected,
+                None => break,
+            }
+            counters.push(counter);
+            bytes = rest;
+        }
+        Ok((bytes, counters, version))
+    }
+
+    /// Genus/version of the code tables the message was parsed with
+    pub fn version(&self) -> &GenusVersion {
+        match self {
+            Message::Custom { version, .. }
+            | Message::Native { version, .. }
+            | Message::Group { version, .. } => version,
+        }
+    }
+
+    /// Genus/version counters read right before the message, in stream order
+    pub fn counters(&self) -> &[Counter] {
+        match self {
+            Message::Custom { counters, .. }
+            | Message::Native { counters, .. }
+            | Message::Group { counters, .. } => counters,
+        }
+    }
+
+    /// Write the genus/version counters read before the message, then the message, custom
+    /// payload as it was read, native body and attachments in `domain` with the counter codes of
+    /// the message version
+    pub fn write_to<W: Write>(&self, writer: &mut W, domain: Domain) -> ParsideResult<()> {
+        for counter in self.counters() {
+            let bytes = match domain {
+                Domain::Text => counter.qb64b()?,
+                Domain::Binary => counter.qb2()?,
+            };
+            writer.write_all(&bytes)?;
+        }
+        let bytes = match (self, domain) {
+            (Message::Custom { value, .. }, _) => value.raw.clone(),
+            (Message::Native { value, .. }, Domain::Text) => value.qb64b()?,
+            (Message::Native { value, .. }, Domain::Binary) => value.qb2()?,
+            (Message::Group { value, version, .. }, Domain::Text) => {
+                value.qb64b_with_version(version)?
+            }
+            (Message::Group { value, version, .. }, Domain::Binary) => {
+                value.qb2_with_version(version)?
+            }
+        };
+        Ok(writer.write_all(&bytes)?)
+    }
+
     /// Get custom payload from parsed message
     pub fn payload(&self) -> ParsideResult<&CustomPayload> {
         match self {
-            Message::Group { .. } => Err(ParsideError::NotExist),
-            Message::Custom { value } => Ok(value),
+            Message::Custom { value, .. } => Ok(value),
+            Message::Native { .. } | Message::Group { .. } => Err(ParsideError::NotExist),
         }
     }
 
     /// Get custom payload converted to specific data type from parsed message
     pub fn typed_payload<D: DeserializeOwned>(&self) -> ParsideResult<D> {
         match self {
-            Message::Group { .. } => Err(ParsideError::NotExist),
-            Message::Custom { value } => value.to_typed_message::<D>(),
+            Message::Custom { value, .. } => value.to_typed_message::<D>(),
+            Message::Native { .. } | Message::Group { .. } => Err(ParsideError::NotExist),
         }
     }
 
     /// Get CESR group from parsed message
     pub fn cesr_group(&self) -> ParsideResult<&CesrGroup> {
         match self {
-            Message::Group { value } => Ok(value),
-            Message::Custom { .. } => Err(ParsideError::NotExist),
+            Message::Group { value, .. } => Ok(value),
+            Message::Custom { .. } | Message::Native { .. } => Err(ParsideError::NotExist),
+        }
+    }
+
+    /// Get CESR 2.0 native body from parsed message
+    pub fn native_body(&self) -> ParsideResult<&NativeBody> {
+        match self {
+            Message::Native { value, .. } => Ok(value),
+            Message::Custom { .. } | Message::Group { .. } => Err(ParsideError::NotExist),
         }
     }
 }
@@ -1,7 +1,11 @@
-use nom::multi::many0;
+use std::io::Write;
 
 use crate::error::{ParsideError, ParsideResult};
-use crate::{nomify, Message};
+use crate::message::custom_payload::Framing;
+use crate::message::domain::Domain;
+use crate::message::event::Events;
+use crate::message::genus_version::GenusVersion;
+use crate::Message;
 
 /// Datastructures representing list of parsed messages
 #[derive(Debug)]
@@ -10,20 +14,115 @@ pub struct MessageList {
 }
 
 impl MessageList {
-    /// Parse multiple messages from provided bytes
-    pub fn from_stream_bytes<'a>(bytes: &'a [u8]) -> ParsideResult<(&'a [u8], MessageList)> {
+    /// Parse multiple messages from provided bytes, leaving over the bytes from the first one not
+    /// starting a complete message. Malformed items of groups fail with [`ParsideError::Parse`].
+    pub fn from_stream_bytes(bytes: &[u8]) -> ParsideResult<(&[u8], MessageList)> {
+        Self::from_stream_bytes_with_framing(bytes, Framing::default())
+    }
+
+    /// Parse multiple messages from provided bytes, finding the end of custom payloads with
+    /// `framing`, see [`MessageList::from_stream_bytes`]
+    pub fn from_stream_bytes_with_framing(
+        bytes: &[u8],
+        framing: Framing,
+    ) -> ParsideResult<(&[u8], MessageList)> {
         if bytes.is_empty() {
             return Err(ParsideError::EmptyBytesStream);
         }
-        let (rest, messages) = many0(nomify!(Message::from_stream_bytes))(bytes)?;
+
+        // code tables selected by a genus/version counter stay active for the following messages
+        let mut version = GenusVersion::default();
+        let mut rest = bytes;
+        let mut messages = vec![];
+        while !rest.is_empty() {
+            match Message::from_stream_bytes_with_version(rest, framing, &version) {
+                Ok((next, message)) => {
+                    version = message.version().clone();
+                    messages.push(message);
+                    rest = next;
+                }
+                // malformed items of groups are reported where they are in `bytes`
+                Err(err @ ParsideError::Parse { .. }) => {
+                    return Err(err.shifted(bytes.len() - rest.len()))
+                }
+                // bytes not starting a message, or of a message cut off, are left over, see
+                // `StreamParser` for parsing messages cut off
+                Err(_) => break,
+            }
+        }
         Ok((rest, MessageList { messages }))
     }
+
+    /// Parse all bytes into messages, failing on bytes that are not a message rather than
+    /// leaving them over. Offsets of errors are counted from the beginning of `bytes`.
+    pub(crate) fn from_complete_stream_bytes(bytes: &[u8]) -> ParsideResult<MessageList> {
+        if bytes.is_empty() {
+            return Err(ParsideError::EmptyBytesStream);
+        }
+
+        let mut version = GenusVersion::default();
+        let mut rest = bytes;
+        let mut messages = vec![];
+        while !rest.is_empty() {
+            let (next, message) =
+                Message::from_stream_bytes_with_version(rest, Framing::default(), &version)
+                    .map_err(|err| err.shifted(bytes.len() - rest.len()))?;
+            version = message.version().clone();
+            messages.push(message);
+            rest = next;
+        }
+        Ok(MessageList { messages })
+    }
+
+    /// Write the messages, custom payloads as they were read, native bodies and attachments in
+    /// `domain`. Genus/version counters are written as they were read, and where the code tables
+    /// of messages change without one.
+    pub fn write_to<W: Write>(&self, writer: &mut W, domain: Domain) -> ParsideResult<()> {
+        let mut version = GenusVersion::default();
+        for message in &self.messages {
+            if !message.counters().is_empty() {
+                version = message.version().clone();
+            } else if message.version() != &version {
+                version = message.version().clone();
+                let counter = version.counter()?;
+                let bytes = match domain {
+                    Domain::Text => counter.qb64b()?,
+                    Domain::Binary => counter.qb2()?,
+                };
+                writer.write_all(&bytes)?;
+            }
+            message.write_to(writer, domain)?;
+        }
+        Ok(())
+    }
+
+    /// Serialize the messages with attachments in text domain, see [`MessageList::write_to`].
+    /// Custom payloads are kept as read, so the result is text only for JSON bodies.
+    pub fn to_qb64(&self) -> ParsideResult<Vec<u8>> {
+        let mut out = vec![];
+        self.write_to(&mut out, Domain::Text)?;
+        Ok(out)
+    }
+
+    /// Serialize the messages with attachments in binary domain, see [`MessageList::write_to`]
+    pub fn to_qb2(&self) -> ParsideResult<Vec<u8>> {
+        let mut out = vec![];
+        self.write_to(&mut out, Domain::Binary)?;
+        Ok(out)
+    }
+
+    /// Iterate over message bodies paired with the groups attached to them
+    pub fn events(&self) -> Events<'_> {
+        Events::new(&self.messages)
+    }
 }
 
 #[cfg(test)]
+#[allow(clippy::redundant_static_lifetimes, clippy::useless_format)]
 pub mod tests {
     use super::*;
     use crate::error::ParsideError;
+    use crate::message::groups::tests as groups;
     use crate::CesrGroup;
 
     const PAYLOAD_1: &'static str = r#"{"v":"1","t":"foo"}"#;
@@ -64,7 +163,7 @@ pub mod tests {
         assert_eq!(1, message_list.messages.len());
         assert!(matches!(
             message_list.messages[0],
-            Message::Group { value: CesrGroup::NonTransReceiptCouplesVariant { .. } }
+            Message::Group { value: CesrGroup::NonTransReceiptCouplesVariant { .. }, .. }
         ));
     }
 
@@ -80,12 +179,12 @@ pub mod tests {
         assert!(matches!(message_list.messages[0], Message::Custom { .. }));
         assert!(matches!(
             message_list.messages[1],
-            Message::Group { value: CesrGroup::NonTransReceiptCouplesVariant { .. } }
+            Message::Group { value: CesrGroup::NonTransReceiptCouplesVariant { .. }, .. }
         ));
         assert!(matches!(message_list.messages[2], Message::Custom { .. }));
         assert!(matches!(
             message_list.messages[3],
-            Message::Group { value: CesrGroup::ControllerIdxSigsVariant { .. } }
+            Message::Group { value: CesrGroup::ControllerIdxSigsVariant { .. }, .. }
         ));
     }
 
@@ -98,7 +197,153 @@ pub mod tests {
         assert!(matches!(message_list.messages[0], Message::Custom { .. }));
         assert!(matches!(
             message_list.messages[1],
-            Message::Group { value: CesrGroup::NonTransReceiptCouplesVariant { .. } }
+            Message::Group { value: CesrGroup::NonTransReceiptCouplesVariant { .. }, .. }
+        ));
+    }
+
+    #[test]
+    pub fn test_parse_message_list_with_truncated_trailing_group() {
+        let stream = format!("{}{}", PAYLOAD_1, &CONTROLLER_IDX_SIGS[..20]);
+        let (rest, message_list) = MessageList::from_stream_bytes(stream.as_bytes()).unwrap();
+        assert_eq!(&CONTROLLER_IDX_SIGS.as_bytes()[..20], rest);
+        assert_eq!(1, message_list.messages.len());
+    }
+
+    #[test]
+    pub fn test_parse_message_list_with_malformed_group() {
+        let mut sigs = CONTROLLER_IDX_SIGS.as_bytes().to_vec();
+        sigs[10] = b'#';
+        let stream = [PAYLOAD_1.as_bytes(), &sigs].concat();
+        match MessageList::from_stream_bytes(&stream).unwrap_err() {
+            ParsideError::Parse { path, offset, .. } => {
+                assert_eq!("ControllerIdxSigs[0]", path);
+                assert_eq!(PAYLOAD_1.len() + 4, offset);
+            }
+            err => panic!("Unexpected error {err:?}"),
+        }
+    }
+
+    #[test]
+    pub fn test_parse_message_list_framed_by_version_string() {
+        let stream = [groups::KERI_EVENT, groups::CONTROLLER_IDX_SIGS, groups::KERI_EVENT].concat();
+        let (rest, message_list) =
+            MessageList::from_stream_bytes_with_framing(&stream, Framing::VersionString).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(3, message_list.messages.len());
+        assert_eq!(groups::KERI_EVENT, message_list.messages[2].payload().unwrap().raw);
+
+        let stream = format!("{}{}", PAYLOAD_1, CONTROLLER_IDX_SIGS);
+        let (rest, message_list) =
+            MessageList::from_stream_bytes_with_framing(stream.as_bytes(), Framing::VersionString)
+                .unwrap();
+        assert_eq!(stream.as_bytes(), rest);
+        assert!(message_list.messages.is_empty());
+    }
+
+    #[test]
+    pub fn test_parse_message_list_switching_genus_version() {
+        let v2_sigs = format!("-KAW{}", &CONTROLLER_IDX_SIGS[4..]);
+        let stream = format!(
+            "{}-_AAACAA{}{}{}--AAABAA{}",
+            PAYLOAD_1, PAYLOAD_2, v2_sigs, PAYLOAD_1, CONTROLLER_IDX_SIGS
+        );
+        let (rest, message_list) = MessageList::from_stream_bytes(stream.as_bytes()).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(5, message_list.messages.len());
+
+        let majors = message_list.messages.iter().map(|message| message.version().major);
+        assert_eq!(vec![1, 2, 2, 2, 1], majors.collect::<Vec<_>>());
+        assert!(matches!(
+            message_list.messages[2],
+            Message::Group { value: CesrGroup::ControllerIdxSigsVariant { .. }, .. }
+        ));
+        assert!(matches!(
+            message_list.messages[4],
+            Message::Group { value: CesrGroup::ControllerIdxSigsVariant { .. }, .. }
         ));
+
+        let stream = format!("{}-_AAADAA{}", PAYLOAD_1, PAYLOAD_2);
+        let (rest, message_list) = MessageList::from_stream_bytes(stream.as_bytes()).unwrap();
+        assert_eq!(1, message_list.messages.len());
+        assert!(rest.starts_with(b"-_AAADAA"));
+    }
+
+    #[test]
+    pub fn test_parse_message_list_with_native_body() {
+        let body = "-GAO0J_tXixn0J_dEADZ055vgh5utgSY3OOL1lW0m1pJ1W0Ia6-SVuGa0OqE";
+        let v2_sigs = format!("-KAW{}", &CONTROLLER_IDX_SIGS[4..]);
+        let stream = format!("-_AAACAA{}{}", body, v2_sigs);
+        let (rest, message_list) = MessageList::from_stream_bytes(stream.as_bytes()).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(2, message_list.messages.len());
+
+        let native = message_list.messages[0].native_body().unwrap();
+        assert_eq!(Some("ixn".to_string()), native.ilk());
+        assert_eq!(body, native.qb64().unwrap());
+        assert!(message_list.messages[1].cesr_group().is_ok());
+
+        // native body codes are attachment groups in the 1.0 table
+        let err = MessageList::from_stream_bytes(body.as_bytes()).unwrap_err();
+        assert!(err.to_string().starts_with("SealSourceCouples[0].seqner: "));
+    }
+
+    #[test]
+    pub fn test_serialize_message_list_round_trip() {
+        let v2_sigs = format!("-KAW{}", &CONTROLLER_IDX_SIGS[4..]);
+        let native = "-GAO0J_tXixn0J_dEADZ055vgh5utgSY3OOL1lW0m1pJ1W0Ia6-SVuGa0OqE";
+        let mut streams = vec![
+            format!(
+                "{}{}{}{}",
+                PAYLOAD_1, NON_TRANS_RECEIPT_COUPLES, PAYLOAD_2, CONTROLLER_IDX_SIGS
+            ),
+            format!(
+                "{}-_AAACAA{}{}{}--AAABAA{}",
+                PAYLOAD_1, PAYLOAD_2, v2_sigs, PAYLOAD_1, CONTROLLER_IDX_SIGS
+            ),
+            format!("-_AAACAA{}{}", native, v2_sigs),
+            // genus/version counters not switching tables are kept as well
+            format!("--AAABAA{}-_AAACAA{}-_AAACAA{}", PAYLOAD_1, native, v2_sigs),
+            format!("-_AAACAA-_AAABAA{}{}", PAYLOAD_1, CONTROLLER_IDX_SIGS),
+        ];
+        let fixtures = [
+            groups::TRANS_IDX_SIG_GROUPS,
+            groups::CONTROLLER_IDX_SIGS,
+            groups::NON_TRANS_RECEIPT_COUPLES,
+            groups::ATTACHED_MATERIAL_QUADLETS,
+            groups::TRANS_LAST_IDX_SIG_GROUPS,
+        ];
+        for fixture in fixtures {
+            streams.push(format!("{}{}", PAYLOAD_1, std::str::from_utf8(fixture).unwrap()));
+        }
+        for stream in streams {
+            let (_, message_list) = MessageList::from_stream_bytes(stream.as_bytes()).unwrap();
+            assert_eq!(stream.as_bytes(), message_list.to_qb64().unwrap());
+
+            let qb2 = message_list.to_qb2().unwrap();
+            assert_ne!(stream.as_bytes(), qb2);
+            let (rest, parsed) = MessageList::from_stream_bytes(&qb2).unwrap();
+            assert!(rest.is_empty());
+            assert_eq!(message_list.messages.len(), parsed.messages.len());
+            assert_eq!(stream.as_bytes(), parsed.to_qb64().unwrap());
+        }
+    }
+
+    #[test]
+    pub fn test_serialize_messages_to_json() {
+        let native = "-GAO0J_tXixn0J_dEADZ055vgh5utgSY3OOL1lW0m1pJ1W0Ia6-SVuGa0OqE";
+        let stream = format!("{}{}-_AAACAA{}", PAYLOAD_1, NON_TRANS_RECEIPT_COUPLES, native);
+        let (_, message_list) = MessageList::from_stream_bytes(stream.as_bytes()).unwrap();
+        let json = serde_json::to_value(&message_list.messages).unwrap();
+
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(PAYLOAD_1).unwrap(),
+            json[0]["Custom"]["value"]["value"]
+        );
+        assert_eq!(1, json[0]["Custom"]["version"]["major"]);
+        assert!(json[1]["Group"]["value"]["NonTransReceiptCouplesVariant"].is_object());
+        let fields = &json[2]["Native"]["value"]["fields"];
+        assert_eq!("t", fields[0]["label"]);
+        assert_eq!("Xixn", fields[0]["value"]["Primitive"]["value"]["qb64"]);
+        assert_eq!(2, json[2]["Native"]["version"]["major"]);
     }
 }
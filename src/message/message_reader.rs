@@ -0,0 +1,125 @@
+use std::io::{ErrorKind, Read};
+
+use crate::error::ParsideResult;
+use crate::message::custom_payload::Framing;
+use crate::message::message::Message;
+use crate::message::stream_parser::{StreamParser, CHUNK_SIZE, MAX_BUFFERED};
+
+/// Blocking iterator over messages read from an `std::io::Read`, e.g. a file or stdin.
+/// The reader is read in chunks as messages need more bytes, holding no more than the window
+/// in memory. Iteration ends at EOF of the reader, or after the first error, reporting a message
+/// cut short by EOF as `Incomplete`.
+#[derive(Debug)]
+pub struct MessageReader<R> {
+    reader: R,
+    parser: StreamParser,
+    chunk: Box<[u8]>,
+    window: usize,
+    done: bool,
+}
+
+impl<R: Read> MessageReader<R> {
+    /// Iterator over messages read from `reader`
+    pub fn new(reader: R) -> Self {
+        Self::with_framing(reader, Framing::default())
+    }
+
+    /// Iterator finding the end of custom payloads with `framing`
+    pub fn with_framing(reader: R, framing: Framing) -> Self {
+        Self {
+            reader,
+            parser: StreamParser::with_framing(framing),
+            chunk: vec![0; CHUNK_SIZE].into_boxed_slice(),
+            window: MAX_BUFFERED,
+            done: false,
+        }
+    }
+
+    /// Limit bytes held in memory, messages larger than `window` fail with
+    /// [`ParsideError::BufferLimit`]
+    pub fn with_window(mut self, window: usize) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Number of bytes read into messages so far, i.e. the offset of the message failing
+    /// iteration in the stream
+    pub fn consumed(&self) -> usize {
+        self.parser.consumed()
+    }
+
+    /// Consume the iterator, returning the underlying reader
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    fn next_message(&mut self) -> ParsideResult<Option<Message>> {
+        loop {
+            if let Some(message) = self.parser.next_message()? {
+                return Ok(Some(message));
+            }
+            let size = self.parser.read_size(self.window)?;
+            let read = match self.reader.read(&mut self.chunk[..size]) {
+                Ok(read) => read,
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err.into()),
+            };
+            if read == 0 {
+                self.done = true;
+                self.parser.check_finished()?;
+                return Ok(None);
+            }
+            self.parser.feed(&self.chunk[..read]);
+        }
+    }
+}
+
+impl<R: Read> Iterator for MessageReader<R> {
+    type Item = ParsideResult<Message>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let next = self.next_message();
+        if next.is_err() {
+            self.done = true;
+        }
+        next.transpose()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::error::ParsideError;
+    use crate::message::groups::tests::{NON_TRANS_RECEIPT_COUPLES, PAYLOAD};
+    use crate::CesrGroup;
+
+    #[test]
+    pub fn test_message_reader_within_window() {
+        let stream = [PAYLOAD, NON_TRANS_RECEIPT_COUPLES].concat().repeat(100);
+        let reader = MessageReader::new(stream.as_slice()).with_window(200);
+        let messages = reader.collect::<ParsideResult<Vec<_>>>().unwrap();
+        assert_eq!(200, messages.len());
+        assert!(matches!(
+            messages[199],
+            Message::Group { value: CesrGroup::NonTransReceiptCouplesVariant { .. }, .. }
+        ));
+    }
+
+    #[test]
+    pub fn test_message_reader_failures() {
+        let stream = [PAYLOAD, NON_TRANS_RECEIPT_COUPLES].concat();
+        let mut reader = MessageReader::new(stream.as_slice()).with_window(64);
+        assert!(reader.next().unwrap().is_ok());
+        assert_eq!(ParsideError::BufferLimit(64), reader.next().unwrap().unwrap_err());
+        assert!(reader.next().is_none());
+
+        let stream = [PAYLOAD, &NON_TRANS_RECEIPT_COUPLES[..50]].concat();
+        let mut reader = MessageReader::new(stream.as_slice());
+        assert!(reader.next().unwrap().is_ok());
+        assert!(matches!(reader.next().unwrap(), Err(ParsideError::Incomplete(..))));
+        assert!(reader.next().is_none());
+    }
+}
@@ -0,0 +1,145 @@
+use std::future::poll_fn;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_core::Stream;
+use tokio::io::{AsyncRead, ReadBuf};
+
+use crate::error::{ParsideError, ParsideResult};
+use crate::message::custom_payload::Framing;
+use crate::message::message::Message;
+use crate::message::stream_parser::{StreamParser, CHUNK_SIZE, MAX_BUFFERED};
+
+/// Stream of messages read from an `AsyncRead`, e.g. a long-lived KERI connection.
+/// Each message is yielded as soon as it is complete. The stream ends at EOF of the reader,
+/// or after the first error, reporting a message cut short by EOF as `Incomplete`.
+#[derive(Debug)]
+pub struct MessageStream<R> {
+    reader: R,
+    parser: StreamParser,
+    chunk: Box<[u8]>,
+    max_buffered: usize,
+    done: bool,
+}
+
+impl<R: AsyncRead + Unpin> MessageStream<R> {
+    /// Stream of messages read from `reader`
+    pub fn new(reader: R) -> Self {
+        Self::with_framing(reader, Framing::default())
+    }
+
+    /// Stream finding the end of custom payloads with `framing`
+    pub fn with_framing(reader: R, framing: Framing) -> Self {
+        Self {
+            reader,
+            parser: StreamParser::with_framing(framing),
+            chunk: vec![0; CHUNK_SIZE].into_boxed_slice(),
+            max_buffered: MAX_BUFFERED,
+            done: false,
+        }
+    }
+
+    /// Limit bytes buffered for a single message, larger messages fail with
+    /// [`ParsideError::BufferLimit`]
+    pub fn with_max_buffered(mut self, max_buffered: usize) -> Self {
+        self.max_buffered = max_buffered;
+        self
+    }
+
+    /// Next message of the stream, `None` once the stream ended
+    pub async fn next_message(&mut self) -> Option<ParsideResult<Message>> {
+        poll_fn(|cx| Pin::new(&mut *self).poll_next(cx)).await
+    }
+
+    /// Consume the stream, returning the underlying reader
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    fn fail(&mut self, err: ParsideError) -> Poll<Option<ParsideResult<Message>>> {
+        self.done = true;
+        Poll::Ready(Some(Err(err)))
+    }
+}
+
+impl<R: AsyncRead + Unpin> Stream for MessageStream<R> {
+    type Item = ParsideResult<Message>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            if this.done {
+                return Poll::Ready(None);
+            }
+
+            match this.parser.next_message() {
+                Ok(Some(message)) => return Poll::Ready(Some(Ok(message))),
+                Ok(None) => {}
+                Err(err) => return this.fail(err),
+            }
+            let size = match this.parser.read_size(this.max_buffered) {
+                Ok(size) => size,
+                Err(err) => return this.fail(err),
+            };
+            let mut buf = ReadBuf::new(&mut this.chunk[..size]);
+            if let Err(err) = ready!(Pin::new(&mut this.reader).poll_read(cx, &mut buf)) {
+                return this.fail(err.into());
+            }
+            if buf.filled().is_empty() {
+                this.done = true;
+                return match this.parser.check_finished() {
+                    Ok(()) => Poll::Ready(None),
+                    Err(err) => Poll::Ready(Some(Err(err))),
+                };
+            }
+            this.parser.feed(buf.filled());
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::message::groups::tests::{NON_TRANS_RECEIPT_COUPLES, PAYLOAD};
+    use crate::CesrGroup;
+    use tokio::io::AsyncWriteExt;
+
+    #[tokio::test]
+    pub async fn test_message_stream_from_connection() {
+        let (mut client, server) = tokio::io::duplex(16);
+        let stream = [PAYLOAD, NON_TRANS_RECEIPT_COUPLES, PAYLOAD].concat();
+        let writer = tokio::spawn(async move {
+            client.write_all(&stream).await.unwrap();
+        });
+
+        let mut messages = MessageStream::new(server);
+        assert!(matches!(messages.next_message().await, Some(Ok(Message::Custom { .. }))));
+        assert!(matches!(
+            messages.next_message().await,
+            Some(Ok(Message::Group { value: CesrGroup::NonTransReceiptCouplesVariant { .. }, .. }))
+        ));
+        assert!(matches!(messages.next_message().await, Some(Ok(Message::Custom { .. }))));
+        writer.await.unwrap();
+        assert!(messages.next_message().await.is_none());
+    }
+
+    #[tokio::test]
+    pub async fn test_message_stream_ending_mid_message() {
+        let stream = [PAYLOAD, &NON_TRANS_RECEIPT_COUPLES[..50]].concat();
+        let mut messages = MessageStream::new(stream.as_slice());
+        assert!(messages.next_message().await.unwrap().is_ok());
+        let err = messages.next_message().await.unwrap().unwrap_err();
+        assert!(matches!(err, ParsideError::Incomplete(..)));
+        assert!(messages.next_message().await.is_none());
+    }
+
+    #[tokio::test]
+    pub async fn test_message_stream_buffer_limit() {
+        let stream = [PAYLOAD, NON_TRANS_RECEIPT_COUPLES].concat();
+        let mut messages = MessageStream::new(stream.as_slice()).with_max_buffered(64);
+        assert!(messages.next_message().await.unwrap().is_ok());
+        let err = messages.next_message().await.unwrap().unwrap_err();
+        assert_eq!(ParsideError::BufferLimit(64), err);
+        assert!(messages.next_message().await.is_none());
+    }
+}
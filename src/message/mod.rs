@@ -1,12 +1,42 @@
+pub mod annotate;
+#[cfg(feature = "tokio")]
+pub mod codec;
+// impls derived by num-derive 0.3 are nested in a const block
+#[allow(non_local_definitions)]
 pub mod cold_code;
+pub mod counter;
 pub mod custom_payload;
+pub mod domain;
+pub mod event;
+pub mod genus_version;
 pub mod groups;
 #[allow(clippy::module_inception)]
 pub mod message;
 pub mod message_list;
+pub mod message_reader;
+#[cfg(feature = "tokio")]
+pub mod message_stream;
+pub mod native_body;
 mod parsers;
+pub mod primitive;
+pub mod stream_parser;
+pub mod texter;
 
-pub use custom_payload::CustomPayload;
+pub use annotate::{annotate, denot};
+#[cfg(feature = "tokio")]
+pub use codec::CesrCodec;
+pub use counter::Counter;
+pub use custom_payload::{CustomPayload, Framing, VersionString};
+pub use domain::{transcode, Domain};
+pub use event::{Event, EventBody, Events};
+pub use genus_version::GenusVersion;
 pub use groups::*;
 pub use message::Message;
 pub use message_list::MessageList;
+pub use message_reader::MessageReader;
+#[cfg(feature = "tokio")]
+pub use message_stream::MessageStream;
+pub use native_body::{NativeBody, NativeField, NativeValue};
+pub use primitive::Primitive;
+pub use stream_parser::StreamParser;
+pub use texter::Texter;
@@ -0,0 +1,371 @@
+use crate::error::{ParsideError, ParsideResult, PathSegment};
+use crate::message::cold_code::ColdCode;
+use crate::message::counter::{CodexV2, Counter};
+use crate::message::groups::MAX_NESTING_DEPTH;
+use crate::message::parsers::Parsers;
+use crate::message::primitive::Primitive;
+use crate::nomify;
+use crate::utils::nom::counted;
+use serde::Serialize;
+
+/// Field labels of fixed field bodies by message type, in KERI 2.0 field order
+const FIXED_FIELDS: [(&str, &[&str]); 6] = [
+    ("icp", &["v", "t", "d", "i", "s", "kt", "k", "nt", "n", "bt", "b", "c", "a"]),
+    ("rot", &["v", "t", "d", "i", "s", "p", "kt", "k", "nt", "n", "bt", "br", "ba", "c", "a"]),
+    ("ixn", &["v", "t", "d", "i", "s", "p", "a"]),
+    ("dip", &["v", "t", "d", "i", "s", "kt", "k", "nt", "n", "bt", "b", "c", "a", "di"]),
+    ("drt", &["v", "t", "d", "i", "s", "p", "kt", "k", "nt", "n", "bt", "br", "ba", "c", "a"]),
+    ("rct", &["v", "t", "d", "i", "s"]),
+];
+
+/// Message body encoded natively in CESR 2.0, either as fixed field (`-F`) or as field map (`-G`)
+/// group. Fields keep the primitives as they were read, so the body re-serialises byte-identically.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NativeBody {
+    /// Counter code of the body, small or big variant of `FixBodyGroup` or `MapBodyGroup`
+    code: String,
+    pub fields: Vec<NativeField>,
+}
+
+/// Labelled field of a native body or of a map nested in it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NativeField {
+    pub label: String,
+    /// Label primitive preceding the value in field maps, `None` in fixed field bodies
+    pub labeler: Option<Primitive>,
+    pub value: NativeValue,
+}
+
+/// Value of a native body field
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum NativeValue {
+    Primitive {
+        value: Primitive,
+    },
+    /// Generic list group (`-J`)
+    List {
+        code: String,
+        value: Vec<NativeValue>,
+    },
+    /// Generic map group (`-I`)
+    Map {
+        code: String,
+        value: Vec<NativeField>,
+    },
+}
+
+impl NativeBody {
+    /// Check whether small counter `code` of the CESR 2.0 table frames a native body
+    pub fn has_code(code: &str) -> bool {
+        [CodexV2::FixBodyGroup, CodexV2::MapBodyGroup].contains(&code)
+    }
+
+    /// Parse native body group from bytes
+    pub fn from_stream_bytes(bytes: &[u8]) -> ParsideResult<(&[u8], NativeBody)> {
+        if bytes.is_empty() {
+            return Err(ParsideError::EmptyBytesStream);
+        }
+        let cold_code = ColdCode::try_from(bytes[0])?;
+        let (rest, counter) = Parsers::counter_parser(&cold_code)?(bytes)?;
+        let small_code = counter.small_code();
+        if !Self::has_code(&small_code) {
+            return Err(ParsideError::Unexpected(format!(
+                "Unexpected counter code {:?} of native body",
+                counter.code()
+            )));
+        }
+
+        let name =
+            if small_code == CodexV2::FixBodyGroup { "FixBodyGroup" } else { "MapBodyGroup" };
+        Self::from_counted_stream_bytes(rest, &counter, &cold_code)
+            .map_err(|err| err.within(PathSegment::Name(name), bytes).with_code(&counter.code()))
+            .map_err(|err| err.located(bytes.len()))
+    }
+
+    fn from_counted_stream_bytes<'a>(
+        bytes: &'a [u8],
+        counter: &Counter,
+        cold_code: &ColdCode,
+    ) -> ParsideResult<(&'a [u8], NativeBody)> {
+        let code = counter.code();
+        if counter.small_code() == CodexV2::MapBodyGroup {
+            let (rest, fields) = NativeField::map_from_stream_bytes(bytes, counter, cold_code, 0)?;
+            return Ok((rest, NativeBody { code, fields }));
+        }
+
+        let (rest, values) = NativeValue::list_from_stream_bytes(bytes, counter, cold_code, 0)?;
+        let ilk = match values.get(1) {
+            Some(NativeValue::Primitive { value }) => value.tag(),
+            _ => None,
+        }
+        .ok_or_else(|| ParsideError::Unexpected("Fixed field body without ilk".to_string()))?;
+        let labels = FIXED_FIELDS
+            .iter()
+            .find(|(fixed_ilk, _)| *fixed_ilk == ilk)
+            .map(|(_, labels)| *labels)
+            .ok_or_else(|| ParsideError::Unexpected(format!("Unknown fields of ilk {ilk:?}")))?;
+        if labels.len() != values.len() {
+            return Err(ParsideError::Unexpected(format!(
+                "Fixed field body of ilk {ilk:?} has {} fields, expected {}",
+                values.len(),
+                labels.len()
+            )));
+        }
+
+        let fields = labels
+            .iter()
+            .zip(values)
+            .map(|(label, value)| NativeField { label: label.to_string(), labeler: None, value })
+            .collect();
+        Ok((rest, NativeBody { code, fields }))
+    }
+
+    /// Whether the body is a fixed field rather than a field map group
+    pub fn is_fixed(&self) -> bool {
+        self.code.ends_with(&CodexV2::FixBodyGroup[1..])
+    }
+
+    /// Value of the field with `label`
+    pub fn get(&self, label: &str) -> Option<&NativeValue> {
+        self.fields.iter().find(|field| field.label == label).map(|field| &field.value)
+    }
+
+    /// Message type of the body, the tag of its `t` field
+    pub fn ilk(&self) -> Option<String> {
+        match self.get("t")? {
+            NativeValue::Primitive { value } => value.tag(),
+            _ => None,
+        }
+    }
+
+    /// Get qb64 representation of the body
+    pub fn qb64(&self) -> ParsideResult<String> {
+        String::from_utf8(self.qb64b()?).map_err(|err| ParsideError::Common(err.to_string()))
+    }
+
+    /// Get qb64b representation of the body
+    pub fn qb64b(&self) -> ParsideResult<Vec<u8>> {
+        let mut body = vec![];
+        for field in &self.fields {
+            field.write_qb64b(&mut body)?;
+        }
+        framed_qb64b(&self.code, body)
+    }
+
+    /// Get qb2 representation of the body
+    pub fn qb2(&self) -> ParsideResult<Vec<u8>> {
+        Counter::qb64b_to_qb2(&self.qb64b()?)
+    }
+
+    /// Get size of qb64 representation of the body
+    pub fn full_size(&self) -> ParsideResult<usize> {
+        Ok(self.qb64b()?.len())
+    }
+}
+
+impl NativeField {
+    fn map_from_stream_bytes<'a>(
+        bytes: &'a [u8],
+        counter: &Counter,
+        cold_code: &ColdCode,
+        depth: usize,
+    ) -> ParsideResult<(&'a [u8], Vec<NativeField>)> {
+        let parse = |bytes| Self::from_stream_bytes(bytes, cold_code, depth);
+        let counter = counter.clone().counting_quadlets();
+        let (rest, value) = counted(nomify!(parse), &counter, cold_code)(bytes)?;
+        Ok((rest, value))
+    }
+
+    fn from_stream_bytes<'a>(
+        bytes: &'a [u8],
+        cold_code: &ColdCode,
+        depth: usize,
+    ) -> ParsideResult<(&'a [u8], NativeField)> {
+        let (rest, labeler) = Parsers::primitive_parser(cold_code)?(bytes)?;
+        let label = labeler.text().ok_or_else(|| {
+            ParsideError::Unexpected(format!("Unexpected label code {:?}", labeler.code()))
+        })?;
+        let (rest, value) = NativeValue::from_stream_bytes(rest, cold_code, depth)
+            .map_err(|err| err.within(PathSegment::Name(&label), rest))?;
+        Ok((rest, NativeField { label, labeler: Some(labeler), value }))
+    }
+
+    fn write_qb64b(&self, out: &mut Vec<u8>) -> ParsideResult<()> {
+        if let Some(labeler) = &self.labeler {
+            out.extend_from_slice(&labeler.qb64b());
+        }
+        self.value.write_qb64b(out)
+    }
+}
+
+impl NativeValue {
+    fn list_from_stream_bytes<'a>(
+        bytes: &'a [u8],
+        counter: &Counter,
+        cold_code: &ColdCode,
+        depth: usize,
+    ) -> ParsideResult<(&'a [u8], Vec<NativeValue>)> {
+        let parse = |bytes| Self::from_stream_bytes(bytes, cold_code, depth);
+        let counter = counter.clone().counting_quadlets();
+        let (rest, value) = counted(nomify!(parse), &counter, cold_code)(bytes)?;
+        Ok((rest, value))
+    }
+
+    fn from_stream_bytes<'a>(
+        bytes: &'a [u8],
+        cold_code: &ColdCode,
+        depth: usize,
+    ) -> ParsideResult<(&'a [u8], NativeValue)> {
+        let is_counter = match cold_code {
+            ColdCode::CtOpB2 => bytes.first().is_some_and(|byte| byte >> 2 == 62),
+            _ => bytes.first() == Some(&b'-'),
+        };
+        if !is_counter {
+            let (rest, value) = Parsers::primitive_parser(cold_code)?(bytes)?;
+            return Ok((rest, NativeValue::Primitive { value }));
+        }
+
+        if depth + 1 >= MAX_NESTING_DEPTH {
+            return Err(ParsideError::Unexpected(format!(
+                "Groups nested more than {MAX_NESTING_DEPTH} levels deep"
+            )));
+        }
+        let (rest, counter) = Parsers::counter_parser(cold_code)?(bytes)?;
+        let code = counter.code();
+        match counter.small_code().as_str() {
+            CodexV2::GenericListGroup => {
+                let (rest, value) =
+                    Self::list_from_stream_bytes(rest, &counter, cold_code, depth + 1)?;
+                Ok((rest, NativeValue::List { code, value }))
+            }
+            CodexV2::GenericMapGroup => {
+                let (rest, value) =
+                    NativeField::map_from_stream_bytes(rest, &counter, cold_code, depth + 1)?;
+                Ok((rest, NativeValue::Map { code, value }))
+            }
+            _ => Err(ParsideError::Unexpected(format!(
+                "Unexpected counter code {code:?} in native body"
+            ))),
+        }
+    }
+
+    pub(crate) fn write_qb64b(&self, out: &mut Vec<u8>) -> ParsideResult<()> {
+        match self {
+            NativeValue::Primitive { value } => out.extend_from_slice(&value.qb64b()),
+            NativeValue::List { code, value } => {
+                let mut body = vec![];
+                for value in value {
+                    value.write_qb64b(&mut body)?;
+                }
+                out.extend_from_slice(&framed_qb64b(code, body)?);
+            }
+            NativeValue::Map { code, value } => {
+                let mut body = vec![];
+                for field in value {
+                    field.write_qb64b(&mut body)?;
+                }
+                out.extend_from_slice(&framed_qb64b(code, body)?);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Prefix qb64 `body` with counter `code` counting its quadlets
+fn framed_qb64b(code: &str, body: Vec<u8>) -> ParsideResult<Vec<u8>> {
+    let counter = Counter::new_with_code_and_count(code, (body.len() / 4) as u32)?;
+    Ok([counter.qb64b()?, body].concat())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use cesride::Seqner;
+
+    const DIGEST: &str = "EFhg5my9DuMU6gw1CVk6QgkmZKBttWSXDzVzWVmxh0_K";
+    const SN: &str = "0AAAAAAAAAAAAAAAAAAAAAAA";
+    const KEY: &str = "DKxy2sgzfplyr-tgwIxS19f2OchFHtLwPWD3v4oYimBx";
+
+    /// Native body with `code` holding `body`, counting its quadlets
+    fn native(code: &str, body: &str) -> String {
+        String::from_utf8(framed_qb64b(code, body.as_bytes().to_vec()).unwrap()).unwrap()
+    }
+
+    #[test]
+    pub fn test_parse_fixed_field_body() {
+        let keys = native(CodexV2::GenericListGroup, KEY);
+        let empty = native(CodexV2::GenericListGroup, "");
+        let fields = [
+            "YKERICAA", "Xicp", DIGEST, DIGEST, SN, "MAAB", &keys, "MAAB", &keys, "MAAA", &empty,
+            &empty, &empty,
+        ];
+        let stream = native(CodexV2::FixBodyGroup, &fields.concat());
+
+        let (rest, body) = NativeBody::from_stream_bytes(stream.as_bytes()).unwrap();
+        assert!(rest.is_empty());
+        assert!(body.is_fixed());
+        assert_eq!(Some("icp".to_string()), body.ilk());
+        match body.get("k").unwrap() {
+            NativeValue::List { value, .. } => assert_eq!(1, value.len()),
+            _ => panic!("Unexpected case"),
+        }
+        match body.get("s").unwrap() {
+            NativeValue::Primitive { value } => {
+                assert_eq!(0, value.to_matter::<Seqner>().unwrap().sn().unwrap())
+            }
+            _ => panic!("Unexpected case"),
+        }
+        assert_eq!(stream, body.qb64().unwrap());
+
+        let qb2 = body.qb2().unwrap();
+        let (rest, parsed) = NativeBody::from_stream_bytes(&qb2).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(body, parsed);
+        assert_eq!(qb2, parsed.qb2().unwrap());
+    }
+
+    #[test]
+    pub fn test_parse_field_map_body() {
+        let seal = native(CodexV2::GenericMapGroup, &["0J_d", DIGEST].concat());
+        let seals = native(CodexV2::GenericListGroup, &seal);
+        let fields = ["0J_v", "YKERICAA", "0J_t", "Xixn", "0J_d", DIGEST, "0J_a", &seals];
+        let stream = native(CodexV2::MapBodyGroup, &fields.concat());
+
+        let (rest, body) = NativeBody::from_stream_bytes(stream.as_bytes()).unwrap();
+        assert!(rest.is_empty());
+        assert!(!body.is_fixed());
+        assert_eq!(Some("ixn".to_string()), body.ilk());
+        assert_eq!(
+            vec!["v", "t", "d", "a"],
+            body.fields.iter().map(|f| &f.label).collect::<Vec<_>>()
+        );
+        match body.get("a").unwrap() {
+            NativeValue::List { value, .. } => match &value[0] {
+                NativeValue::Map { value, .. } => assert_eq!("d", value[0].label),
+                _ => panic!("Unexpected case"),
+            },
+            _ => panic!("Unexpected case"),
+        }
+        assert_eq!(stream, body.qb64().unwrap());
+    }
+
+    #[test]
+    pub fn test_parse_malformed_native_bodies() {
+        // fields of unknown ilk
+        let stream = native(CodexV2::FixBodyGroup, "YKERICAAXfoo");
+        assert!(NativeBody::from_stream_bytes(stream.as_bytes()).is_err());
+
+        // label of a map has to be text
+        let stream = native(CodexV2::MapBodyGroup, &[DIGEST, DIGEST].concat());
+        assert!(NativeBody::from_stream_bytes(stream.as_bytes()).is_err());
+
+        // field overrunning counted quadlets
+        let stream = format!("-GAB0J_d{}", DIGEST);
+        let err = NativeBody::from_stream_bytes(stream.as_bytes()).unwrap_err();
+        assert!(matches!(err.root_cause(), ParsideError::Unexpected(..)));
+
+        let stream = format!("-GAM0J_d{}", &DIGEST[..20]);
+        let err = NativeBody::from_stream_bytes(stream.as_bytes()).unwrap_err();
+        assert!(matches!(err, ParsideError::Incomplete(..)));
+    }
+}
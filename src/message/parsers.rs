@@ -1,15 +1,22 @@
 use crate::error::{ParsideError, ParsideResult};
 use crate::message::cold_code::ColdCode;
+use crate::message::counter::Counter;
+use crate::message::primitive::{Material, Primitive};
+use crate::message::texter::Texter;
 use crate::nomify;
+use crate::utils::nom::counted;
 use cesride::{
-    Cigar, Counter, Dater, Diger, Indexer, Matter, Pather, Prefixer, Saider, Seqner, Siger, Verfer,
+    Cigar, Dater, Diger, Indexer, Matter, Pather, Prefixer, Saider, Seqner, Siger, Verfer,
 };
-use nom::multi::count;
+use nom::Parser;
+
+/// Size of the largest fixed size primitive, indexed signatures of the "3A" and "3B" codes
+const MAX_FIXED_QB64_SIZE: usize = 160;
 
 /// Parser's of CESR primitives
 pub struct Parsers {}
 
-pub type ParserRet<'a, T> = fn(&'a [u8]) -> nom::IResult<&'a [u8], T>;
+pub type ParserRet<'a, T> = fn(&'a [u8]) -> nom::IResult<&'a [u8], T, ParsideError>;
 
 impl Parsers {
     pub(crate) fn pather_parser<'a>(cold_code: &ColdCode) -> ParsideResult<ParserRet<'a, Pather>> {
@@ -21,13 +28,13 @@ impl Parsers {
     }
 
     fn pather_from_qb64b(bytes: &[u8]) -> ParsideResult<(&[u8], Pather)> {
-        let matter = (Pather::new_with_qb64b)(bytes)?;
+        let matter = Self::extract_qb64b(bytes, Pather::new_with_qb64b)?;
         let size = matter.full_size()?;
         Ok((&bytes[size..], matter))
     }
 
     fn pather_from_qb2(bytes: &[u8]) -> ParsideResult<(&[u8], Pather)> {
-        let matter = (Pather::new_with_qb2)(bytes)?;
+        let matter = Self::extract_qb2(bytes, Pather::new_with_qb2)?;
         let size = matter.full_size()? / 4 * 3;
         Ok((&bytes[size..], matter))
     }
@@ -42,13 +49,13 @@ impl Parsers {
     }
 
     fn diger_from_qb64b(bytes: &[u8]) -> ParsideResult<(&[u8], Diger)> {
-        let matter = (Diger::new_with_qb64b)(bytes)?;
+        let matter = Self::extract_qb64b(bytes, Diger::new_with_qb64b)?;
         let size = matter.full_size()?;
         Ok((&bytes[size..], matter))
     }
 
     fn diger_from_qb2(bytes: &[u8]) -> ParsideResult<(&[u8], Diger)> {
-        let matter = (Diger::new_with_qb2)(bytes)?;
+        let matter = Self::extract_qb2(bytes, Diger::new_with_qb2)?;
         let size = matter.full_size()? / 4 * 3;
         Ok((&bytes[size..], matter))
     }
@@ -62,13 +69,13 @@ impl Parsers {
     }
 
     fn siger_from_qb64b(bytes: &[u8]) -> ParsideResult<(&[u8], Siger)> {
-        let matter = Siger::new_with_qb64b(bytes, None)?;
+        let matter = Self::extract_qb64b(bytes, |bytes| Siger::new_with_qb64b(bytes, None))?;
         let size = matter.full_size()?;
         Ok((&bytes[size..], matter))
     }
 
     fn siger_from_qb2(bytes: &[u8]) -> ParsideResult<(&[u8], Siger)> {
-        let matter = Siger::new_with_qb2(bytes, None)?;
+        let matter = Self::extract_qb2(bytes, |bytes| Siger::new_with_qb2(bytes, None))?;
         let size = matter.full_size()? / 4 * 3;
         Ok((&bytes[size..], matter))
     }
@@ -82,19 +89,20 @@ impl Parsers {
     }
 
     fn cigar_from_qb64b(bytes: &[u8]) -> ParsideResult<(&[u8], Cigar)> {
-        let verfer = Verfer::new_with_qb64b(bytes)?;
+        let verfer = Self::extract_qb64b(bytes, Verfer::new_with_qb64b)?;
         let size = verfer.full_size()?;
         let bytes = &bytes[size..];
-        let cigar = Cigar::new_with_qb64b(bytes, Some(&verfer))?;
+        let cigar =
+            Self::extract_qb64b(bytes, |bytes| Cigar::new_with_qb64b(bytes, Some(&verfer)))?;
         let size = cigar.full_size()?;
         Ok((&bytes[size..], cigar))
     }
 
     fn cigar_from_qb2(bytes: &[u8]) -> ParsideResult<(&[u8], Cigar)> {
-        let verfer = Verfer::new_with_qb2(bytes)?;
+        let verfer = Self::extract_qb2(bytes, Verfer::new_with_qb2)?;
         let size = verfer.full_size()? / 4 * 3;
         let bytes = &bytes[size..];
-        let cigar = Cigar::new_with_qb2(bytes, Some(&verfer))?;
+        let cigar = Self::extract_qb2(bytes, |bytes| Cigar::new_with_qb2(bytes, Some(&verfer)))?;
         let size = cigar.full_size()? / 4 * 3;
         Ok((&bytes[size..], cigar))
     }
@@ -110,13 +118,13 @@ impl Parsers {
     }
 
     fn prefixer_from_qb64b(bytes: &[u8]) -> ParsideResult<(&[u8], Prefixer)> {
-        let matter = (Prefixer::new_with_qb64b)(bytes)?;
+        let matter = Self::extract_qb64b(bytes, Prefixer::new_with_qb64b)?;
         let size = matter.full_size()?;
         Ok((&bytes[size..], matter))
     }
 
     fn prefixer_from_qb2(bytes: &[u8]) -> ParsideResult<(&[u8], Prefixer)> {
-        let matter = (Prefixer::new_with_qb2)(bytes)?;
+        let matter = Self::extract_qb2(bytes, Prefixer::new_with_qb2)?;
         let size = matter.full_size()? / 4 * 3;
         Ok((&bytes[size..], matter))
     }
@@ -130,13 +138,13 @@ impl Parsers {
     }
 
     fn seqner_from_qb64b(bytes: &[u8]) -> ParsideResult<(&[u8], Seqner)> {
-        let matter = (Seqner::new_with_qb64b)(bytes)?;
+        let matter = Self::extract_qb64b(bytes, Seqner::new_with_qb64b)?;
         let size = matter.full_size()?;
         Ok((&bytes[size..], matter))
     }
 
     fn seqner_from_qb2(bytes: &[u8]) -> ParsideResult<(&[u8], Seqner)> {
-        let matter = (Seqner::new_with_qb2)(bytes)?;
+        let matter = Self::extract_qb2(bytes, Seqner::new_with_qb2)?;
         let size = matter.full_size()? / 4 * 3;
         Ok((&bytes[size..], matter))
     }
@@ -150,13 +158,13 @@ impl Parsers {
     }
 
     fn dater_from_qb64b(bytes: &[u8]) -> ParsideResult<(&[u8], Dater)> {
-        let matter = (Dater::new_with_qb64b)(bytes)?;
+        let matter = Self::extract_qb64b(bytes, Dater::new_with_qb64b)?;
         let size = matter.full_size()?;
         Ok((&bytes[size..], matter))
     }
 
     fn dater_from_qb2(bytes: &[u8]) -> ParsideResult<(&[u8], Dater)> {
-        let matter = (Dater::new_with_qb2)(bytes)?;
+        let matter = Self::extract_qb2(bytes, Dater::new_with_qb2)?;
         let size = matter.full_size()? / 4 * 3;
         Ok((&bytes[size..], matter))
     }
@@ -170,17 +178,96 @@ impl Parsers {
     }
 
     fn saider_from_qb64b(bytes: &[u8]) -> ParsideResult<(&[u8], Saider)> {
-        let matter = (Saider::new_with_qb64b)(bytes)?;
+        let matter = Self::extract_qb64b(bytes, Saider::new_with_qb64b)?;
         let size = matter.full_size()?;
         Ok((&bytes[size..], matter))
     }
 
     fn saider_from_qb2(bytes: &[u8]) -> ParsideResult<(&[u8], Saider)> {
-        let matter = (Saider::new_with_qb2)(bytes)?;
+        let matter = Self::extract_qb2(bytes, Saider::new_with_qb2)?;
+        let size = matter.full_size()? / 4 * 3;
+        Ok((&bytes[size..], matter))
+    }
+
+    pub(crate) fn texter_parser<'a>(cold_code: &ColdCode) -> ParsideResult<ParserRet<'a, Texter>> {
+        match cold_code {
+            ColdCode::CtB64 | ColdCode::OpB64 => Ok(nomify!(Self::texter_from_qb64b)),
+            ColdCode::CtOpB2 => Ok(nomify!(Self::texter_from_qb2)),
+            _ => Err(ParsideError::Unexpected("Unexpected cold code".to_string())),
+        }
+    }
+
+    fn texter_from_qb64b(bytes: &[u8]) -> ParsideResult<(&[u8], Texter)> {
+        let matter = Self::extract_qb64b(bytes, |bytes| {
+            Self::validate_texter(Texter::new_with_qb64b(bytes)?)
+        })?;
+        let size = matter.full_size()?;
+        Ok((&bytes[size..], matter))
+    }
+
+    fn texter_from_qb2(bytes: &[u8]) -> ParsideResult<(&[u8], Texter)> {
+        let matter =
+            Self::extract_qb2(bytes, |bytes| Self::validate_texter(Texter::new_with_qb2(bytes)?))?;
         let size = matter.full_size()? / 4 * 3;
         Ok((&bytes[size..], matter))
     }
 
+    fn validate_texter(texter: Texter) -> cesride::Result<Texter> {
+        if !Texter::has_code(&texter.code()) {
+            return Err(cesride::Error::UnexpectedCode(texter.code()).into());
+        }
+        Ok(texter)
+    }
+
+    pub(crate) fn primitive_parser<'a>(
+        cold_code: &ColdCode,
+    ) -> ParsideResult<ParserRet<'a, Primitive>> {
+        match cold_code {
+            ColdCode::CtB64 | ColdCode::OpB64 => Ok(nomify!(Self::primitive_from_qb64b)),
+            ColdCode::CtOpB2 => Ok(nomify!(Self::primitive_from_qb2)),
+            _ => Err(ParsideError::Unexpected("Unexpected cold code".to_string())),
+        }
+    }
+
+    pub(crate) fn primitive_from_qb64b(bytes: &[u8]) -> ParsideResult<(&[u8], Primitive)> {
+        let (code, size) = match Primitive::tag_size(bytes)? {
+            Some((code, size)) => (code.to_string(), size),
+            None => {
+                let matter = Self::extract_qb64b(bytes, Material::new_with_qb64b)?;
+                (matter.code(), matter.full_size()?)
+            }
+        };
+        if bytes.len() < size {
+            return Err(ParsideError::Incomplete(size - bytes.len()));
+        }
+        // tags are not validated by cesride, their soft part has to be Base64 still
+        if let Some(byte) =
+            bytes[..size].iter().find(|byte| Self::qb64b_prefix(&[**byte]).is_empty())
+        {
+            return Err(Self::invalid::<Primitive>(format!(
+                "unexpected byte {byte:#04x} in Base64 material"
+            )));
+        }
+        let qb64 = String::from_utf8(bytes[..size].to_vec())
+            .map_err(|err| ParsideError::Common(err.to_string()))?;
+        Ok((&bytes[size..], Primitive::new(code, qb64)))
+    }
+
+    pub(crate) fn primitive_from_qb2(bytes: &[u8]) -> ParsideResult<(&[u8], Primitive)> {
+        if bytes.len() < 3 {
+            return Err(ParsideError::Incomplete(3 - bytes.len()));
+        }
+        let size = match Primitive::tag_size(&Counter::qb2_to_qb64b(&bytes[..3]))? {
+            Some((_, size)) => size / 4 * 3,
+            None => Self::extract_qb2(bytes, Material::new_with_qb2)?.full_size()? / 4 * 3,
+        };
+        if bytes.len() < size {
+            return Err(ParsideError::Incomplete(size - bytes.len()));
+        }
+        let (_, primitive) = Self::primitive_from_qb64b(&Counter::qb2_to_qb64b(&bytes[..size]))?;
+        Ok((&bytes[size..], primitive))
+    }
+
     pub(crate) fn counter_parser<'a>(
         cold_code: &ColdCode,
     ) -> ParsideResult<ParserRet<'a, Counter>> {
@@ -192,38 +279,122 @@ impl Parsers {
     }
 
     fn counter_from_qb64b(bytes: &[u8]) -> ParsideResult<(&[u8], Counter)> {
-        let counter = Counter::new(None, None, None, Some(bytes), None, None)?;
+        let counter = Counter::new_with_qb64b(bytes)?;
         let size = counter.full_size()?;
         Ok((&bytes[size..], counter))
     }
 
     fn counter_from_qb2(bytes: &[u8]) -> ParsideResult<(&[u8], Counter)> {
-        let counter = Counter::new(None, None, None, None, None, Some(bytes))?;
+        let counter = Counter::new_with_qb2(bytes)?;
         let size = counter.full_size()? / 4 * 3;
         Ok((&bytes[size..], counter))
     }
 
+    /// Parser of indexed signatures following their own counter, which counts quadlets rather
+    /// than signatures when `counts_quadlets` is set for CESR 2.0 streams
     pub(crate) fn siger_list_parser<'a>(
         cold_code: &ColdCode,
-    ) -> ParsideResult<ParserRet<'a, Vec<Siger>>> {
-        match cold_code {
-            ColdCode::CtB64 | ColdCode::OpB64 => Ok(nomify!(Self::siger_list_from_qb64b)),
-            ColdCode::CtOpB2 => Ok(nomify!(Self::siger_list_from_q2)),
-            _ => Err(ParsideError::Unexpected("Unexpected cold code".to_string())),
+        counts_quadlets: bool,
+    ) -> ParsideResult<impl Parser<&'a [u8], Vec<Siger>, ParsideError>> {
+        let counter_parser = Self::counter_parser(cold_code)?;
+        let siger_parser = Self::siger_parser(cold_code)?;
+        let cold_code = cold_code.clone();
+        Ok(move |bytes: &'a [u8]| {
+            let (rest, counter) = counter_parser(bytes)?;
+            let counter = if counts_quadlets { counter.counting_quadlets() } else { counter };
+            counted(siger_parser, &counter, &cold_code)(rest)
+                .map_err(|err| err.map(|err| err.with_code(&counter.code())))
+        })
+    }
+
+    /// Extract primitive from the Base64 prefix of bytes. Bytes cesride could not slice as chars
+    /// or variable size material it could not decode are reported as errors rather than passed on.
+    fn extract_qb64b<T>(
+        bytes: &[u8],
+        extract: impl Fn(&[u8]) -> cesride::Result<T>,
+    ) -> ParsideResult<T> {
+        // cesride processes all the bytes it is handed, so fixed size primitives are extracted
+        // from a window and only variable size material from the rest of the stream
+        if bytes.len() > MAX_FIXED_QB64_SIZE {
+            match Self::extract_qb64b_window(&bytes[..MAX_FIXED_QB64_SIZE], &extract) {
+                Err(ParsideError::Incomplete(_)) => {}
+                result => return result,
+            }
         }
+        Self::extract_qb64b_window(bytes, &extract)
     }
 
-    fn siger_list_from_qb64b<'a>(bytes: &'a [u8]) -> ParsideResult<(&'a [u8], Vec<Siger>)> {
-        let (rest, counter) = Self::counter_from_qb64b(bytes)?;
-        let (rest, values) =
-            count(nomify!(Parsers::siger_from_qb64b), counter.count() as usize)(rest)?;
-        Ok((rest, values))
+    fn extract_qb64b_window<T>(
+        bytes: &[u8],
+        extract: &impl Fn(&[u8]) -> cesride::Result<T>,
+    ) -> ParsideResult<T> {
+        let qb64b = Self::qb64b_prefix(bytes);
+        let empty_lead = match qb64b.first() {
+            Some(b'5' | b'6') => qb64b.get(2..4),
+            Some(b'8' | b'9') => qb64b.get(4..8),
+            _ => None,
+        };
+        if empty_lead.is_some_and(|soft| soft.iter().all(|char| *char == b'A')) {
+            return Err(Self::invalid::<T>("empty variable size material with lead bytes"));
+        }
+
+        extract(qb64b).map_err(|err| match ParsideError::from_extraction(err) {
+            ParsideError::Incomplete(_) if qb64b.len() < bytes.len() => Self::invalid::<T>(
+                format!("unexpected byte {:#04x} in Base64 material", bytes[qb64b.len()]),
+            ),
+            err @ ParsideError::Incomplete(_) => err,
+            err => Self::invalid::<T>(err),
+        })
+    }
+
+    /// Extract primitive from qualified base2 bytes, see [`Parsers::extract_qb64b`]
+    fn extract_qb2<T>(
+        bytes: &[u8],
+        extract: impl Fn(&[u8]) -> cesride::Result<T>,
+    ) -> ParsideResult<T> {
+        if bytes.len() > MAX_FIXED_QB64_SIZE / 4 * 3 {
+            match Self::extract_qb2_window(&bytes[..MAX_FIXED_QB64_SIZE / 4 * 3], &extract) {
+                Err(ParsideError::Incomplete(_)) => {}
+                result => return result,
+            }
+        }
+        Self::extract_qb2_window(bytes, &extract)
+    }
+
+    fn extract_qb2_window<T>(
+        bytes: &[u8],
+        extract: &impl Fn(&[u8]) -> cesride::Result<T>,
+    ) -> ParsideResult<T> {
+        // sextets 57, 58, 60 and 61 are the '5', '6', '8' and '9' hard codes
+        let empty_lead = match bytes.first().map(|byte| byte >> 2) {
+            Some(57 | 58) => bytes.get(1..3).map(|soft| soft[0] & 0x0f == 0 && soft[1] == 0),
+            Some(60 | 61) => bytes.get(3..6).map(|soft| soft.iter().all(|byte| *byte == 0)),
+            _ => None,
+        };
+        if empty_lead.unwrap_or(false) {
+            return Err(Self::invalid::<T>("empty variable size material with lead bytes"));
+        }
+
+        extract(bytes).map_err(|err| match ParsideError::from_extraction(err) {
+            err @ ParsideError::Incomplete(_) => err,
+            err => Self::invalid::<T>(err),
+        })
+    }
+
+    /// Error of primitive of type `T` which could not be extracted
+    fn invalid<T>(reason: impl ToString) -> ParsideError {
+        let primitive = std::any::type_name::<T>().rsplit("::").next().unwrap_or_default();
+        ParsideError::InvalidPrimitive {
+            primitive: primitive.to_string(),
+            reason: reason.to_string(),
+        }
     }
 
-    fn siger_list_from_q2<'a>(bytes: &'a [u8]) -> ParsideResult<(&'a [u8], Vec<Siger>)> {
-        let (rest, counter) = Parsers::counter_from_qb2(bytes)?;
-        let (rest, values) =
-            count(nomify!(Parsers::siger_from_qb2), counter.count() as usize)(rest)?;
-        Ok((rest, values))
+    fn qb64b_prefix(bytes: &[u8]) -> &[u8] {
+        let size = bytes
+            .iter()
+            .position(|byte| !(byte.is_ascii_alphanumeric() || *byte == b'-' || *byte == b'_'))
+            .unwrap_or(bytes.len());
+        &bytes[..size]
     }
 }
@@ -0,0 +1,177 @@
+use cesride::matter::Codex as MatterCodex;
+use cesride::{Bext, Bexter, Matter};
+use serde::Serialize;
+
+use crate::error::{ParsideError, ParsideResult};
+use crate::message::counter::Counter;
+use crate::message::parsers::Parsers;
+use crate::message::texter::Texter;
+
+/// Tag codes of the CESR 2.0 table unknown to cesride, with the size of the soft part carrying
+/// the tag. The single character of `0J` tags is padded with a leading `_`.
+const TAGS: [(&str, usize); 4] = [("0J", 2), ("0K", 2), ("X", 3), ("Y", 7)];
+
+/// Primitive of any code kept in its qualified Base64 form, e.g. field of a native message body
+/// whose type is told by its label only. Convert it to a typed primitive with
+/// [`Primitive::to_matter`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Primitive {
+    code: String,
+    qb64: String,
+}
+
+impl Primitive {
+    /// Primitive from qualified Base64 text holding exactly one primitive
+    pub fn new_with_qb64(qb64: &str) -> ParsideResult<Self> {
+        let (rest, primitive) = Parsers::primitive_from_qb64b(qb64.as_bytes())?;
+        if !rest.is_empty() {
+            return Err(ParsideError::Unexpected(format!(
+                "Unexpected {} bytes following primitive",
+                rest.len()
+            )));
+        }
+        Ok(primitive)
+    }
+
+    /// Code of the primitive
+    pub fn code(&self) -> String {
+        self.code.clone()
+    }
+
+    /// Get qb64 representation of the primitive
+    pub fn qb64(&self) -> String {
+        self.qb64.clone()
+    }
+
+    /// Get qb64b representation of the primitive
+    pub fn qb64b(&self) -> Vec<u8> {
+        self.qb64.as_bytes().to_vec()
+    }
+
+    /// Get qb2 representation of the primitive
+    pub fn qb2(&self) -> ParsideResult<Vec<u8>> {
+        Counter::qb64b_to_qb2(self.qb64.as_bytes())
+    }
+
+    /// Get size of qb64 representation of the primitive
+    pub fn full_size(&self) -> usize {
+        self.qb64.len()
+    }
+
+    /// Tag carried by the primitive, `None` unless it has one of the tag codes
+    pub fn tag(&self) -> Option<String> {
+        let (code, _) = TAGS.iter().find(|(code, _)| *code == self.code)?;
+        let soft = &self.qb64[code.len()..];
+        let tag = if *code == "0J" { &soft[1..] } else { soft };
+        Some(tag.to_string())
+    }
+
+    /// Text of tag, Base64 string or byte string primitives, e.g. label of a map body field
+    pub fn text(&self) -> Option<String> {
+        if let Some(tag) = self.tag() {
+            return Some(tag);
+        }
+        let bext_codes = [
+            MatterCodex::StrB64_L0,
+            MatterCodex::StrB64_L1,
+            MatterCodex::StrB64_L2,
+            MatterCodex::StrB64_Big_L0,
+            MatterCodex::StrB64_Big_L1,
+            MatterCodex::StrB64_Big_L2,
+        ];
+        if bext_codes.contains(&self.code.as_str()) {
+            return Bexter::new_with_qb64(&self.qb64).ok()?.bext().ok();
+        }
+        if Texter::has_code(&self.code) {
+            return String::from_utf8(self.to_matter::<Texter>().ok()?.text()).ok();
+        }
+        None
+    }
+
+    /// Convert to typed cesride primitive, e.g. `Diger` or `Seqner`
+    pub fn to_matter<M: Matter>(&self) -> ParsideResult<M> {
+        Ok(M::new_with_qb64(&self.qb64)?)
+    }
+
+    pub(crate) fn new(code: String, qb64: String) -> Self {
+        Self { code, qb64 }
+    }
+
+    /// Code and full size of the tag primitive starting qb64 bytes, `None` for other codes
+    pub(crate) fn tag_size(bytes: &[u8]) -> ParsideResult<Option<(&'static str, usize)>> {
+        if bytes.is_empty() {
+            return Err(ParsideError::Incomplete(4));
+        }
+        let tag = TAGS.iter().find(|(code, _)| bytes.starts_with(code.as_bytes()));
+        Ok(tag.map(|(code, soft)| (*code, code.len() + soft)))
+    }
+}
+
+/// Material of any code known to cesride, used to size and validate primitives
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Material {
+    code: String,
+    raw: Vec<u8>,
+    size: u32,
+}
+
+impl Matter for Material {
+    fn code(&self) -> String {
+        self.code.clone()
+    }
+
+    fn size(&self) -> u32 {
+        self.size
+    }
+
+    fn raw(&self) -> Vec<u8> {
+        self.raw.clone()
+    }
+
+    fn set_code(&mut self, code: &str) {
+        self.code = code.to_string();
+    }
+
+    fn set_size(&mut self, size: u32) {
+        self.size = size;
+    }
+
+    fn set_raw(&mut self, raw: &[u8]) {
+        self.raw = raw.to_vec();
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use cesride::Diger;
+
+    #[test]
+    pub fn test_primitive_round_trip() {
+        let digest = "EFhg5my9DuMU6gw1CVk6QgkmZKBttWSXDzVzWVmxh0_K";
+        let primitive = Primitive::new_with_qb64(digest).unwrap();
+        assert_eq!("E", primitive.code());
+        assert_eq!(None, primitive.tag());
+        assert_eq!(digest, primitive.to_matter::<Diger>().unwrap().qb64().unwrap());
+
+        let qb2 = primitive.qb2().unwrap();
+        let (rest, parsed) = Parsers::primitive_from_qb2(&qb2).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(primitive, parsed);
+    }
+
+    #[test]
+    pub fn test_tag_primitives() {
+        let (rest, ilk) = Parsers::primitive_from_qb64b(b"XicpEFhg").unwrap();
+        assert_eq!(b"EFhg", rest);
+        assert_eq!(Some("icp".to_string()), ilk.tag());
+
+        let label = Primitive::new_with_qb64("0J_k").unwrap();
+        assert_eq!(Some("k".to_string()), label.text());
+        let (_, parsed) = Parsers::primitive_from_qb2(&label.qb2().unwrap()).unwrap();
+        assert_eq!(label, parsed);
+
+        assert!(matches!(Parsers::primitive_from_qb64b(b"Xic"), Err(ParsideError::Incomplete(1))));
+        assert!(Primitive::new_with_qb64("Xi#p").is_err());
+    }
+}
@@ -0,0 +1,232 @@
+use crate::error::{ParsideError, ParsideResult};
+use crate::message::custom_payload::Framing;
+use crate::message::genus_version::GenusVersion;
+use crate::message::message::Message;
+
+/// Size of chunks read from readers feeding the parser
+pub(crate) const CHUNK_SIZE: usize = 8 * 1024;
+/// Default limit of bytes buffered for a single message
+pub(crate) const MAX_BUFFERED: usize = 16 * 1024 * 1024;
+
+/// Stateful parser consuming CESR stream delivered in chunks
+#[derive(Debug, Default)]
+pub struct StreamParser {
+    buffer: Vec<u8>,
+    needed: usize,
+    framing: Framing,
+    /// Code tables selected by the last genus/version counter of the stream
+    version: GenusVersion,
+    /// Number of bytes parsed into messages so far
+    consumed: usize,
+}
+
+impl StreamParser {
+    /// Parser constructor
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parser finding the end of custom payloads with `framing`
+    pub fn with_framing(framing: Framing) -> Self {
+        Self { framing, ..Self::default() }
+    }
+
+    /// Append bytes read from the stream
+    pub fn feed(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+        self.needed = self.needed.saturating_sub(bytes.len());
+    }
+
+    /// Parse next complete message from buffered bytes.
+    /// Returns `Ok(None)` when more bytes have to be fed first, see [`StreamParser::needed`].
+    /// Malformed input is reported as error, located from the beginning of the stream,
+    /// and discards the buffered bytes.
+    pub fn next_message(&mut self) -> ParsideResult<Option<Message>> {
+        if self.buffer.is_empty() || self.needed > 0 {
+            return Ok(None);
+        }
+
+        match Message::from_stream_bytes_with_version(&self.buffer, self.framing, &self.version) {
+            Ok((rest, message)) => {
+                let consumed = self.buffer.len() - rest.len();
+                self.buffer.drain(..consumed);
+                self.consumed += consumed;
+                self.version = message.version().clone();
+                Ok(Some(message))
+            }
+            Err(ParsideError::Incomplete(needed)) => {
+                self.needed = needed;
+                Ok(None)
+            }
+            Err(err) => {
+                self.buffer.clear();
+                Err(err.shifted(self.consumed))
+            }
+        }
+    }
+
+    /// Number of bytes to read from the stream when no message is complete, holding no more than
+    /// `limit` bytes buffered. Fails with [`ParsideError::BufferLimit`] when the pending message
+    /// does not fit.
+    pub(crate) fn read_size(&self, limit: usize) -> ParsideResult<usize> {
+        if self.buffered() + self.needed().max(1) > limit {
+            return Err(ParsideError::BufferLimit(limit));
+        }
+        // never buffer more than the limit, even if complete messages would fit in the chunk
+        Ok(CHUNK_SIZE.min(limit - self.buffered()))
+    }
+
+    /// Minimum number of bytes still missing to complete pending message, zero if nothing is pending
+    pub fn needed(&self) -> usize {
+        self.needed
+    }
+
+    /// Number of buffered bytes not yet parsed into a message
+    pub fn buffered(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Number of bytes parsed into messages so far, i.e. the offset of the pending message in
+    /// the stream
+    pub fn consumed(&self) -> usize {
+        self.consumed
+    }
+
+    /// Finish parsing, failing if the stream ended in the middle of a message
+    pub fn finish(self) -> ParsideResult<()> {
+        self.check_finished()
+    }
+
+    /// Fail if the stream ended in the middle of a message, keeping the parser
+    pub(crate) fn check_finished(&self) -> ParsideResult<()> {
+        if self.buffer.is_empty() {
+            Ok(())
+        } else {
+            Err(ParsideError::Incomplete(self.needed.max(1)))
+        }
+    }
+}
+
+impl Iterator for StreamParser {
+    type Item = ParsideResult<Message>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_message().transpose()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::CesrGroup;
+
+    use crate::message::groups::tests::{
+        ATTACHED_MATERIAL_QUADLETS, NON_TRANS_RECEIPT_COUPLES, PAYLOAD,
+    };
+
+    #[test]
+    pub fn test_stream_parser_byte_by_byte() {
+        let stream = [PAYLOAD, NON_TRANS_RECEIPT_COUPLES, PAYLOAD].concat();
+        let mut parser = StreamParser::new();
+        let mut messages = vec![];
+        for byte in &stream {
+            parser.feed(&[*byte]);
+            while let Some(message) = parser.next_message().unwrap() {
+                messages.push(message);
+            }
+        }
+
+        assert_eq!(3, messages.len());
+        assert!(matches!(messages[0], Message::Custom { .. }));
+        assert!(matches!(
+            messages[1],
+            Message::Group { value: CesrGroup::NonTransReceiptCouplesVariant { .. }, .. }
+        ));
+        assert!(matches!(messages[2], Message::Custom { .. }));
+        assert!(parser.finish().is_ok());
+    }
+
+    #[test]
+    pub fn test_stream_parser_reports_needed_bytes() {
+        let mut parser = StreamParser::new();
+        parser.feed(&ATTACHED_MATERIAL_QUADLETS[..100]);
+        assert!(parser.next().is_none());
+        assert_eq!(ATTACHED_MATERIAL_QUADLETS.len() - 100, parser.needed());
+
+        parser.feed(&ATTACHED_MATERIAL_QUADLETS[100..]);
+        assert_eq!(0, parser.needed());
+        let message = parser.next().unwrap().unwrap();
+        assert!(matches!(
+            message,
+            Message::Group { value: CesrGroup::AttachedMaterialQuadletsVariant { .. }, .. }
+        ));
+        assert_eq!(0, parser.buffered());
+    }
+
+    #[test]
+    pub fn test_stream_parser_waits_for_declared_size() {
+        let body = format!(r#"{{"v":"KERI10JSON000000_","t":"rpy","x":"{}"}}"#, "a".repeat(4096));
+        let body = body.replace("000000", &format!("{:06x}", body.len()));
+        let mut parser = StreamParser::new();
+        parser.feed(&body.as_bytes()[..100]);
+        assert!(parser.next().is_none());
+        assert_eq!(body.len() - 100, parser.needed());
+
+        // chunks short of the declared size are buffered without parsing
+        for chunk in body.as_bytes()[100..].chunks(1000) {
+            assert!(parser.next().is_none());
+            parser.feed(chunk);
+        }
+        assert_eq!(0, parser.needed());
+        assert!(matches!(parser.next().unwrap().unwrap(), Message::Custom { .. }));
+        assert!(parser.finish().is_ok());
+    }
+
+    #[test]
+    pub fn test_stream_parser_truncated_counter() {
+        let mut parser = StreamParser::new();
+        parser.feed(b"-");
+        assert!(parser.next().is_none());
+        assert_eq!(3, parser.needed());
+        assert_eq!(ParsideError::Incomplete(3), parser.finish().unwrap_err());
+    }
+
+    #[test]
+    pub fn test_stream_parser_malformed_input() {
+        let mut parser = StreamParser::new();
+        parser.feed(b"-ZAB");
+        // ESSR payload group waits for its payload
+        assert!(parser.next().is_none());
+        parser.feed(b"####");
+        assert!(parser.next().unwrap().is_err());
+        assert_eq!(0, parser.buffered());
+        assert!(parser.next().is_none());
+    }
+
+    #[test]
+    pub fn test_stream_parser_unknown_counter_code() {
+        let mut parser = StreamParser::new();
+        parser.feed(b"-WAB");
+        assert!(parser.next().unwrap().is_err());
+        assert_eq!(0, parser.buffered());
+        assert!(parser.next().is_none());
+    }
+
+    #[test]
+    pub fn test_stream_parser_locates_error_in_stream() {
+        let mut malformed = NON_TRANS_RECEIPT_COUPLES.to_vec();
+        malformed[50] = b'#';
+        let mut parser = StreamParser::new();
+        parser.feed(PAYLOAD);
+        parser.feed(&malformed);
+
+        assert!(parser.next().unwrap().is_ok());
+        match parser.next().unwrap().unwrap_err() {
+            ParsideError::Parse { path, offset, .. } => {
+                assert_eq!("NonTransReceiptCouples[0]", path);
+                assert_eq!(PAYLOAD.len() + 4, offset);
+            }
+            err => panic!("Unexpected error {err:?}"),
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use cesride::matter::Codex as MatterCodex;
+use cesride::Matter;
+
+/// Variable size byte string material, e.g. encrypted ESSR payload.
+/// Not provided by cesride, only byte string codes are accepted by [`Texter::has_code`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Texter {
+    code: String,
+    raw: Vec<u8>,
+    size: u32,
+}
+
+impl Default for Texter {
+    fn default() -> Self {
+        Texter { code: MatterCodex::Bytes_L0.to_string(), raw: vec![], size: 0 }
+    }
+}
+
+impl Texter {
+    /// Check whether `code` is one of the byte string codes
+    pub fn has_code(code: &str) -> bool {
+        [
+            MatterCodex::Bytes_L0,
+            MatterCodex::Bytes_L1,
+            MatterCodex::Bytes_L2,
+            MatterCodex::Bytes_Big_L0,
+            MatterCodex::Bytes_Big_L1,
+            MatterCodex::Bytes_Big_L2,
+        ]
+        .contains(&code)
+    }
+
+    /// Get the carried bytes
+    pub fn text(&self) -> Vec<u8> {
+        self.raw()
+    }
+}
+
+impl Matter for Texter {
+    fn code(&self) -> String {
+        self.code.clone()
+    }
+
+    fn size(&self) -> u32 {
+        self.size
+    }
+
+    fn raw(&self) -> Vec<u8> {
+        self.raw.clone()
+    }
+
+    fn set_code(&mut self, code: &str) {
+        self.code = code.to_string();
+    }
+
+    fn set_size(&mut self, size: u32) {
+        self.size = size;
+    }
+
+    fn set_raw(&mut self, raw: &[u8]) {
+        self.raw = raw.to_vec();
+    }
+}
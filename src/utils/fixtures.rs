@@ -0,0 +1,66 @@
+//! Signers and key events shared by the tests of verification and KEL validation
+
+use cesride::{Diger, Matter, Serder, Signer};
+use serde_json::{json, Value as JsonValue};
+
+/// Signer of the key seeded with `seed`
+pub(crate) fn signer(seed: u8, transferable: bool) -> Signer {
+    Signer::new_with_raw(&[seed; 32], Some(transferable), None).unwrap()
+}
+
+/// Signers of the keys seeded with `0..count`
+pub(crate) fn signers(count: u8, transferable: bool) -> Vec<Signer> {
+    (0..count).map(|seed| signer(seed, transferable)).collect()
+}
+
+/// Public keys of `signers`
+pub(crate) fn keys(signers: &[&Signer]) -> Vec<String> {
+    signers.iter().map(|signer| signer.verfer().qb64().unwrap()).collect()
+}
+
+/// Digests of the public keys of `signers`, committing to them as next keys
+pub(crate) fn digests(signers: &[&Signer]) -> Vec<String> {
+    signers
+        .iter()
+        .map(|signer| {
+            Diger::new_with_ser(&signer.verfer().qb64b().unwrap(), None).unwrap().qb64().unwrap()
+        })
+        .collect()
+}
+
+/// Serialize `ked` with its version string sized and its digest computed over the event with
+/// the `labels` dummied, e.g. both `i` and `d` of self-addressing inceptions
+pub(crate) fn saidify(mut ked: JsonValue, labels: &[&str]) -> Serder {
+    let dummy = "#".repeat(44);
+    for label in labels {
+        ked[label] = json!(dummy);
+    }
+    ked["v"] = json!("KERI10JSON000000_");
+    let size = serde_json::to_vec(&ked).unwrap().len();
+    ked["v"] = json!(format!("KERI10JSON{size:06x}_"));
+    let raw = serde_json::to_vec(&ked).unwrap();
+    let said = Diger::new_with_ser(&raw, None).unwrap().qb64().unwrap();
+    for label in labels {
+        ked[label] = json!(said);
+    }
+    Serder::new_with_raw(&serde_json::to_vec(&ked).unwrap()).unwrap()
+}
+
+/// Self-addressing inception of `signers`, committing to `next` and witnessed by `witnesses`
+/// with a witness threshold of `bt`
+pub(crate) fn incept(
+    signers: &[&Signer],
+    next: &[&Signer],
+    witnesses: &[&Signer],
+    bt: &str,
+) -> Serder {
+    saidify(
+        json!({
+            "v": "", "t": "icp", "d": "", "i": "", "s": "0",
+            "kt": format!("{:x}", signers.len()), "k": keys(signers),
+            "nt": format!("{:x}", next.len()), "n": digests(next),
+            "bt": bt, "b": keys(witnesses), "c": [], "a": []
+        }),
+        &["i", "d"],
+    )
+}
@@ -1,2 +1,5 @@
 #[macro_use]
 pub mod nom;
+#[cfg(test)]
+pub(crate) mod fixtures;
+pub(crate) mod serde;
@@ -1,11 +1,93 @@
+use crate::error::{ParsideError, PathSegment};
+use crate::message::cold_code::ColdCode;
+use crate::message::counter::Counter;
+use nom::{IResult, Needed, Parser};
+
 // Helper macros to map function returning parside result to nom compatible
 #[macro_export]
 macro_rules! nomify {
     ($func:expr) => {
         |bytes: &'a [u8]| {
-            $func(bytes).map_err(|_| {
-                nom::Err::Error(nom::error::Error::new(bytes, nom::error::ErrorKind::IsNot))
+            $func(bytes).map_err(|err| match err {
+                $crate::error::ParsideError::Incomplete(needed) => {
+                    nom::Err::Incomplete(nom::Needed::new(needed))
+                }
+                err => nom::Err::Error(err),
             })
         }
     };
 }
+
+/// Like `nom::multi::count`, recording the index of the failing item in its error
+pub(crate) fn items<'a, O, F>(
+    mut parser: F,
+    count: usize,
+) -> impl FnMut(&'a [u8]) -> IResult<&'a [u8], Vec<O>, ParsideError>
+where
+    F: Parser<&'a [u8], O, ParsideError>,
+{
+    move |bytes: &'a [u8]| {
+        let mut rest = bytes;
+        let mut out = vec![];
+        for index in 0..count {
+            let (next, item) = parser
+                .parse(rest)
+                .map_err(|err| err.map(|err| err.within(PathSegment::Index(index), rest)))?;
+            out.push(item);
+            rest = next;
+        }
+        Ok((rest, out))
+    }
+}
+
+/// Items of a group counted by `counter`, either the number of items or, for CESR 2.0 counters,
+/// the quadlets the items fill exactly
+pub(crate) fn counted<'a, O, F>(
+    mut parser: F,
+    counter: &Counter,
+    cold_code: &ColdCode,
+) -> impl FnMut(&'a [u8]) -> IResult<&'a [u8], Vec<O>, ParsideError>
+where
+    F: Parser<&'a [u8], O, ParsideError>,
+{
+    let count = counter.count() as usize;
+    let frame_size = counter.counts_quadlets().then(|| count * cold_code.quadlet_size());
+    move |bytes: &'a [u8]| {
+        let Some(size) = frame_size else {
+            return items(|bytes| parser.parse(bytes), count)(bytes);
+        };
+        if bytes.len() < size {
+            return Err(nom::Err::Incomplete(Needed::new(size - bytes.len())));
+        }
+
+        let (frame, rest) = bytes.split_at(size);
+        let mut remaining = frame;
+        let mut out = vec![];
+        while !remaining.is_empty() {
+            let (next, item) = parser.parse(remaining).map_err(|err| match err {
+                nom::Err::Incomplete(_) => nom::Err::Error(ParsideError::Unexpected(format!(
+                    "Group items overrun {count} counted quadlets"
+                ))),
+                err => err.map(|err| {
+                    err.within(PathSegment::Index(out.len()), remaining).shifted(rest.len())
+                }),
+            })?;
+            out.push(item);
+            remaining = next;
+        }
+        Ok((rest, out))
+    }
+}
+
+/// Record the `name` of the parsed field in errors of the parser
+pub(crate) fn field<'a, O, F>(
+    name: &'static str,
+    mut parser: F,
+) -> impl FnMut(&'a [u8]) -> IResult<&'a [u8], O, ParsideError>
+where
+    F: Parser<&'a [u8], O, ParsideError>,
+{
+    move |bytes: &'a [u8]| {
+        parser.parse(bytes).map_err(|err| err.map(|err| err.within(PathSegment::Name(name), bytes)))
+    }
+}
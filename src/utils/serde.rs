@@ -0,0 +1,120 @@
+//! Serde representations of the primitives of CESR groups, used with `#[serde(with = "...")]`.
+//! Primitives serialize as their code together with their qb64, deserializing back into the
+//! same primitive fails if the code does not match the qb64 or the qb64 carries trailing
+//! characters.
+
+use serde::de::Error as _;
+use serde::ser::Error as _;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// Code and qualified Base64 of a primitive
+#[derive(Serialize, Deserialize)]
+struct Qualified {
+    code: String,
+    qb64: String,
+}
+
+impl Qualified {
+    /// Check `code` and `qb64` of the primitive deserialized from the qb64 match
+    fn checked<E: de::Error>(&self, code: String, qb64: String) -> Result<(), E> {
+        if code != self.code {
+            return Err(E::custom(format!(
+                "code {:?} does not match qb64 {:?}",
+                self.code, self.qb64
+            )));
+        }
+        if qb64 != self.qb64 {
+            return Err(E::custom(format!(
+                "qb64 {:?} does not match primitive {qb64:?}",
+                self.qb64
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Primitives implementing cesride `Matter`, e.g. `Prefixer` or `Saider`
+pub(crate) mod matter {
+    use super::*;
+    use cesride::Matter;
+
+    pub(crate) fn serialize<M: Matter, S: Serializer>(matter: &M, s: S) -> Result<S::Ok, S::Error> {
+        let qb64 = matter.qb64().map_err(S::Error::custom)?;
+        Qualified { code: matter.code(), qb64 }.serialize(s)
+    }
+
+    pub(crate) fn deserialize<'de, M: Matter, D: Deserializer<'de>>(d: D) -> Result<M, D::Error> {
+        let qualified = Qualified::deserialize(d)?;
+        let matter = M::new_with_qb64(&qualified.qb64).map_err(D::Error::custom)?;
+        qualified.checked(matter.code(), matter.qb64().map_err(D::Error::custom)?)?;
+        Ok(matter)
+    }
+}
+
+/// Indexed signatures implementing cesride `Indexer`
+pub(crate) mod indexer {
+    use super::*;
+    use cesride::Indexer;
+
+    pub(crate) fn serialize<I: Indexer, S: Serializer>(
+        indexer: &I,
+        s: S,
+    ) -> Result<S::Ok, S::Error> {
+        let qb64 = indexer.qb64().map_err(S::Error::custom)?;
+        Qualified { code: indexer.code(), qb64 }.serialize(s)
+    }
+
+    pub(crate) fn deserialize<'de, I: Indexer, D: Deserializer<'de>>(d: D) -> Result<I, D::Error> {
+        let qualified = Qualified::deserialize(d)?;
+        let indexer = I::new_with_qb64(&qualified.qb64).map_err(D::Error::custom)?;
+        qualified.checked(indexer.code(), indexer.qb64().map_err(D::Error::custom)?)?;
+        Ok(indexer)
+    }
+}
+
+/// Non-transferable signatures, together with the verifier of the signing key
+pub(crate) mod cigar {
+    use super::*;
+    use cesride::{Cigar, Matter, Verfer};
+
+    #[derive(Serialize, Deserialize)]
+    struct QualifiedCigar {
+        #[serde(with = "super::matter")]
+        verfer: Verfer,
+        #[serde(flatten)]
+        cigar: Qualified,
+    }
+
+    pub(crate) fn serialize<S: Serializer>(cigar: &Cigar, s: S) -> Result<S::Ok, S::Error> {
+        let qb64 = cigar.qb64().map_err(S::Error::custom)?;
+        QualifiedCigar { verfer: cigar.verfer(), cigar: Qualified { code: cigar.code(), qb64 } }
+            .serialize(s)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Cigar, D::Error> {
+        let qualified = QualifiedCigar::deserialize(d)?;
+        let cigar = Cigar::new_with_qb64(&qualified.cigar.qb64, Some(&qualified.verfer))
+            .map_err(D::Error::custom)?;
+        qualified.cigar.checked(cigar.code(), cigar.qb64().map_err(D::Error::custom)?)?;
+        Ok(cigar)
+    }
+}
+
+/// Counters nested in group items
+pub(crate) mod counter {
+    use super::*;
+    use crate::message::counter::Counter;
+
+    pub(crate) fn serialize<S: Serializer>(counter: &Counter, s: S) -> Result<S::Ok, S::Error> {
+        let qb64 = counter.qb64().map_err(S::Error::custom)?;
+        Qualified { code: counter.code(), qb64 }.serialize(s)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Counter, D::Error> {
+        let qualified = Qualified::deserialize(d)?;
+        let counter =
+            Counter::new_with_qb64b(qualified.qb64.as_bytes()).map_err(D::Error::custom)?;
+        qualified.checked(counter.code(), counter.qb64().map_err(D::Error::custom)?)?;
+        Ok(counter)
+    }
+}
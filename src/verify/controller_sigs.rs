@@ -0,0 +1,157 @@
+use cesride::{Indexer, Siger, Tholder, Verfer};
+
+use crate::error::{ParsideError, ParsideResult};
+use crate::message::{ControllerIdxSigs, Group};
+use crate::verify::report::VerificationReport;
+
+/// Verify controller signatures over the raw `body` of an event against the `verfers` of the
+/// establishment event in force, and check the verified indices against its signing threshold.
+/// Duplicate signatures are verified once, signatures indexed past the end of `verfers` fail.
+/// Weighted thresholds not weighing every key of `verfers` are an error.
+pub fn verify_controller_sigs(
+    body: &[u8],
+    sigs: &ControllerIdxSigs,
+    verfers: &[Verfer],
+    tholder: &Tholder,
+) -> ParsideResult<VerificationReport> {
+    let (verified, failed) =
+        verify_sigers(body, sigs.value().iter().map(|sig| &sig.siger), verfers)?;
+    let satisfied = satisfy(tholder, verfers, &verified)?;
+    Ok(VerificationReport { verified, failed, satisfied })
+}
+
+/// Check `verified` indices of `verfers` against signing threshold `tholder`
+pub(crate) fn satisfy(
+    tholder: &Tholder,
+    verfers: &[Verfer],
+    verified: &[u32],
+) -> ParsideResult<bool> {
+    if tholder.weighted() && tholder.size() as usize != verfers.len() {
+        return Err(ParsideError::Unexpected(format!(
+            "Weighted threshold of {} weights for {} keys",
+            tholder.size(),
+            verfers.len()
+        )));
+    }
+    Ok(tholder.satisfy(verified)?)
+}
+
+/// Split indices of `sigers` into those verifying `body` with the verfer at their index and those
+/// failing to, malformed signatures included
+pub(crate) fn verify_sigers<'a>(
+    body: &[u8],
+    sigers: impl IntoIterator<Item = &'a Siger>,
+    verfers: &[Verfer],
+) -> ParsideResult<(Vec<u32>, Vec<u32>)> {
+    let mut seen: Vec<&Siger> = vec![];
+    let mut verified = vec![];
+    let mut failed = vec![];
+    for siger in sigers {
+        if seen.contains(&siger) {
+            continue;
+        }
+        seen.push(siger);
+
+        let index = siger.index();
+        if verified.contains(&index) {
+            continue;
+        }
+        match verfers.get(index as usize) {
+            Some(verfer) if verfer.verify(&siger.raw(), body).unwrap_or(false) => {
+                verified.push(index);
+                failed.retain(|failed| *failed != index);
+            }
+            _ if !failed.contains(&index) => failed.push(index),
+            _ => {}
+        }
+    }
+    Ok((verified, failed))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::message::ControllerIdxSig;
+    use crate::utils::fixtures::signers;
+    use cesride::data::dat;
+    use cesride::{indexer, matter, Signer};
+
+    const BODY: &[u8] = br#"{"v":"KERI10JSON00006a_","t":"ixn"}"#;
+
+    fn sigs(sigers: Vec<Siger>) -> ControllerIdxSigs {
+        ControllerIdxSigs::new(sigers.into_iter().map(|siger| ControllerIdxSig { siger }).collect())
+    }
+
+    #[test]
+    pub fn test_verify_controller_sigs() {
+        let signers = signers(3, true);
+        let verfers = signers.iter().map(Signer::verfer).collect::<Vec<_>>();
+        let tholder = Tholder::new_with_sith(&dat!("2")).unwrap();
+
+        let sig_0 = signers[0].sign_indexed(BODY, false, 0, None).unwrap();
+        let sig_2 = signers[2].sign_indexed(BODY, false, 2, None).unwrap();
+        let sigs = sigs(vec![sig_0.clone(), sig_0, sig_2]);
+        let report = verify_controller_sigs(BODY, &sigs, &verfers, &tholder).unwrap();
+        assert_eq!(
+            VerificationReport { verified: vec![0, 2], failed: vec![], satisfied: true },
+            report
+        );
+
+        let report = verify_controller_sigs(b"other body", &sigs, &verfers, &tholder).unwrap();
+        assert_eq!(vec![0, 2], report.failed);
+        assert!(!report.satisfied);
+    }
+
+    #[test]
+    pub fn test_verify_controller_sigs_out_of_range() {
+        let signers = signers(3, true);
+        let verfers = signers.iter().map(Signer::verfer).take(2).collect::<Vec<_>>();
+        let tholder = Tholder::new_with_sith(&dat!(["1/2", "1/2"])).unwrap();
+
+        // index of the last key is out of range for two keys, wrong key signing at index 1
+        let sigs = sigs(vec![
+            signers[0].sign_indexed(BODY, false, 0, None).unwrap(),
+            signers[2].sign_indexed(BODY, false, 2, None).unwrap(),
+            signers[2].sign_indexed(BODY, false, 1, None).unwrap(),
+        ]);
+        let report = verify_controller_sigs(BODY, &sigs, &verfers, &tholder).unwrap();
+        assert_eq!(vec![0], report.verified);
+        assert_eq!(vec![2, 1], report.failed);
+        assert!(!report.satisfied);
+    }
+
+    #[test]
+    pub fn test_verify_controller_sigs_with_malformed_sig() {
+        let signer =
+            Signer::new_with_raw(&[1; 32], Some(true), Some(matter::Codex::ECDSA_256k1_Seed))
+                .unwrap();
+        let verfers = vec![signer.verfer()];
+        let tholder = Tholder::new_with_sith(&dat!("1")).unwrap();
+
+        // a zero-filled signature is no valid secp256k1 signature at all
+        let junk =
+            Siger::new_with_raw(&[0; 64], None, Some(0), None, Some(indexer::Codex::ECDSA_256k1))
+                .unwrap();
+        let sigs = sigs(vec![junk, signer.sign_indexed(BODY, false, 0, None).unwrap()]);
+        let report = verify_controller_sigs(BODY, &sigs, &verfers, &tholder).unwrap();
+        assert_eq!(
+            VerificationReport { verified: vec![0], failed: vec![], satisfied: true },
+            report
+        );
+
+        let report = verify_controller_sigs(b"other body", &sigs, &verfers, &tholder).unwrap();
+        assert_eq!(vec![0], report.failed);
+    }
+
+    #[test]
+    pub fn test_verify_controller_sigs_with_mismatched_weights() {
+        let signers = signers(3, true);
+        let verfers = signers.iter().map(Signer::verfer).collect::<Vec<_>>();
+        let tholder = Tholder::new_with_sith(&dat!(["1/2", "1/2"])).unwrap();
+
+        // the last key has no weight
+        let sigs = sigs(vec![signers[2].sign_indexed(BODY, false, 2, None).unwrap()]);
+        let err = verify_controller_sigs(BODY, &sigs, &verfers, &tholder).unwrap_err();
+        assert!(matches!(err, ParsideError::Unexpected(..)));
+    }
+}
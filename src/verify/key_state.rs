@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+
+use cesride::{Sadder, Serder, Tholder, Verfer};
+
+use crate::error::{ParsideError, ParsideResult};
+
+/// Signing keys and threshold established by a key event of a transferable prefix
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyState {
+    pub prefix: String,
+    /// Sequence number of the establishment event
+    pub sn: u128,
+    /// Digest of the establishment event
+    pub said: String,
+    pub verfers: Vec<Verfer>,
+    pub tholder: Tholder,
+}
+
+impl KeyState {
+    /// Key state established by the inception or rotation event `serder`
+    pub fn from_establishment(serder: &Serder) -> ParsideResult<Self> {
+        if !serder.est()? {
+            return Err(ParsideError::Unexpected(format!(
+                "Event {} is not an establishment event",
+                serder.said()?
+            )));
+        }
+        let tholder = serder.tholder()?.ok_or_else(|| {
+            ParsideError::Unexpected("Establishment event without signing threshold".to_string())
+        })?;
+        Ok(Self {
+            prefix: serder.pre()?,
+            sn: serder.sn()?,
+            said: serder.said()?,
+            verfers: serder.verfers()?,
+            tholder,
+        })
+    }
+}
+
+/// Source of the key states transferable signatures are verified against, e.g. a KEL database
+pub trait KeyStateProvider {
+    /// Key state established by the event of `prefix` at `sn`, `None` unless the event is known
+    /// and its digest is `said`
+    fn establishment(&self, prefix: &str, sn: u128, said: &str) -> Option<KeyState>;
+}
+
+/// Key states held in memory
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryKeyStates {
+    states: HashMap<(String, u128), KeyState>,
+}
+
+impl InMemoryKeyStates {
+    /// Empty key state store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add key state, replacing the state established at the same prefix and sequence number
+    pub fn insert(&mut self, state: KeyState) {
+        self.states.insert((state.prefix.clone(), state.sn), state);
+    }
+}
+
+impl KeyStateProvider for InMemoryKeyStates {
+    fn establishment(&self, prefix: &str, sn: u128, said: &str) -> Option<KeyState> {
+        self.states.get(&(prefix.to_string(), sn)).filter(|state| state.said == said).cloned()
+    }
+}
@@ -0,0 +1,11 @@
+pub mod controller_sigs;
+pub mod key_state;
+pub mod receipts;
+pub mod report;
+pub mod trans_sigs;
+
+pub use controller_sigs::verify_controller_sigs;
+pub use key_state::{InMemoryKeyStates, KeyState, KeyStateProvider};
+pub use receipts::{verify_non_trans_receipts, verify_witness_receipts, verify_witness_sigs};
+pub use report::{ReceiptReport, TransSignerReport, VerificationReport};
+pub use trans_sigs::{verify_trans_idx_sig_groups, verify_trans_receipts};
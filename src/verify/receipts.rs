@@ -0,0 +1,183 @@
+use cesride::common::Ilkage;
+use cesride::{Matter, Sadder, Serder, Verfer};
+
+use crate::error::{ParsideError, ParsideResult};
+use crate::message::{Group, NonTransReceiptCouples, WitnessIdxSigs};
+use crate::verify::controller_sigs::verify_sigers;
+use crate::verify::report::{ReceiptReport, VerificationReport};
+
+/// Verify the signatures of non-transferable receipt couples over the raw receipted `body`,
+/// each against the verfer carried by its couple. Receiptors are reported once, transferable
+/// ones fail.
+pub fn verify_non_trans_receipts(
+    body: &[u8],
+    couples: &NonTransReceiptCouples,
+) -> ParsideResult<ReceiptReport> {
+    let mut report = ReceiptReport::default();
+    for couple in couples.value() {
+        let verfer = couple.cigar.verfer();
+        let prefix = verfer.qb64()?;
+        if report.verified.contains(&prefix) {
+            continue;
+        }
+        // malformed signatures fail like signatures not verifying
+        if !verfer.transferable() && verfer.verify(&couple.cigar.raw(), body).unwrap_or(false) {
+            report.failed.retain(|failed| *failed != prefix);
+            report.verified.push(prefix);
+        } else if !report.failed.contains(&prefix) {
+            report.failed.push(prefix);
+        }
+    }
+    Ok(report)
+}
+
+/// Verify witness signatures over the raw `body` of an event against the `witnesses` of the
+/// event, indexed by their position in the list, and check the verified signatures reach the
+/// witness threshold `toad`
+pub fn verify_witness_sigs(
+    body: &[u8],
+    sigs: &WitnessIdxSigs,
+    witnesses: &[Verfer],
+    toad: u32,
+) -> ParsideResult<VerificationReport> {
+    let (verified, failed) =
+        verify_sigers(body, sigs.value().iter().map(|sig| &sig.siger), witnesses)?;
+    let satisfied = verified.len() >= toad as usize;
+    Ok(VerificationReport { verified, failed, satisfied })
+}
+
+/// Verify witness signatures over an inception event against its `b` witness list and `bt`
+/// threshold. Later establishment events only amend the list, their witnesses come from the key
+/// state and are verified with [`verify_witness_sigs`], so they fail here.
+pub fn verify_witness_receipts(
+    serder: &Serder,
+    sigs: &WitnessIdxSigs,
+) -> ParsideResult<VerificationReport> {
+    let ilk = serder.ked()["t"].to_string()?;
+    if ilk != Ilkage::icp && ilk != Ilkage::dip {
+        return Err(ParsideError::Unexpected(format!(
+            "Witnesses of {ilk} events come from the key state, see verify_witness_sigs"
+        )));
+    }
+    let witnesses = serder.werfers()?;
+    verify_witness_sigs(&serder.raw(), sigs, &witnesses, toad(serder)?)
+}
+
+/// Witness threshold of an event, hex encoded in its `bt` field
+pub(crate) fn toad(serder: &Serder) -> ParsideResult<u32> {
+    let bt = serder.ked()["bt"].to_string().map_err(|_| {
+        ParsideError::Unexpected("Event does not declare witness threshold".to_string())
+    })?;
+    u32::from_str_radix(&bt, 16)
+        .map_err(|_| ParsideError::Unexpected(format!("Malformed witness threshold {bt:?}")))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::message::{NonTransReceiptCouple, WitnessIdxSig};
+    use crate::utils::fixtures::{incept, saidify, signer, signers};
+    use cesride::{matter, Cigar, Signer};
+    use serde_json::json;
+
+    #[test]
+    pub fn test_verify_non_trans_receipts() {
+        let body = b"receipted body";
+        let witnesses = signers(3, false);
+        let couples = NonTransReceiptCouples::new(
+            [&witnesses[0], &witnesses[1], &witnesses[0]]
+                .into_iter()
+                .map(|w| NonTransReceiptCouple { cigar: w.sign_unindexed(body).unwrap() })
+                .chain([NonTransReceiptCouple {
+                    cigar: witnesses[2].sign_unindexed(b"other body").unwrap(),
+                }])
+                .collect(),
+        );
+        let report = verify_non_trans_receipts(body, &couples).unwrap();
+        let prefixes = witnesses.iter().map(|w| w.verfer().qb64().unwrap()).collect::<Vec<_>>();
+        assert_eq!(prefixes[..2], report.verified);
+        assert_eq!(prefixes[2..], report.failed);
+
+        // transferable receiptors fail even with valid signatures
+        let transferable = signer(3, true);
+        let couples = NonTransReceiptCouples::new(vec![NonTransReceiptCouple {
+            cigar: transferable.sign_unindexed(body).unwrap(),
+        }]);
+        let report = verify_non_trans_receipts(body, &couples).unwrap();
+        assert!(report.verified.is_empty());
+        assert_eq!(vec![transferable.verfer().qb64().unwrap()], report.failed);
+    }
+
+    #[test]
+    pub fn test_verify_non_trans_receipts_with_malformed_sig() {
+        let body = b"receipted body";
+        let ecdsa =
+            Signer::new_with_raw(&[1; 32], Some(false), Some(matter::Codex::ECDSA_256k1_Seed))
+                .unwrap();
+        let witness = signer(0, false);
+        let junk = |signer: &Signer| {
+            let cigar = Cigar::new(
+                Some(&signer.verfer()),
+                Some(matter::Codex::ECDSA_256k1_Sig),
+                Some(&[0; 64]),
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+            NonTransReceiptCouple { cigar }
+        };
+        let couples = NonTransReceiptCouples::new(vec![
+            junk(&ecdsa),
+            NonTransReceiptCouple { cigar: witness.sign_unindexed(body).unwrap() },
+        ]);
+        let report = verify_non_trans_receipts(body, &couples).unwrap();
+        assert_eq!(vec![witness.verfer().qb64().unwrap()], report.verified);
+        assert_eq!(vec![ecdsa.verfer().qb64().unwrap()], report.failed);
+
+        let couples = NonTransReceiptCouples::new(vec![
+            junk(&ecdsa),
+            NonTransReceiptCouple { cigar: ecdsa.sign_unindexed(body).unwrap() },
+        ]);
+        let report = verify_non_trans_receipts(body, &couples).unwrap();
+        assert_eq!(vec![ecdsa.verfer().qb64().unwrap()], report.verified);
+        assert!(report.failed.is_empty());
+    }
+
+    #[test]
+    pub fn test_verify_witness_receipts() {
+        let witnesses = signers(3, false);
+        let serder = incept(&[&signer(9, true)], &[], &witnesses.iter().collect::<Vec<_>>(), "2");
+        let raw = serder.raw();
+        let sig = |i: usize| WitnessIdxSig {
+            siger: witnesses[i].sign_indexed(&raw, false, i as u32, None).unwrap(),
+        };
+
+        let report = verify_witness_receipts(&serder, &WitnessIdxSigs::new(vec![sig(0)])).unwrap();
+        assert_eq!(vec![0], report.verified);
+        assert!(!report.satisfied);
+
+        let sigs = WitnessIdxSigs::new(vec![sig(0), sig(2)]);
+        let report = verify_witness_receipts(&serder, &sigs).unwrap();
+        assert_eq!(vec![0, 2], report.verified);
+        assert!(report.satisfied);
+
+        // witness indices refer into the backer list of the event
+        let verfers = [witnesses[2].verfer(), witnesses[0].verfer()];
+        let report = verify_witness_sigs(&raw, &sigs, &verfers, 1).unwrap();
+        assert_eq!(vec![0, 2], report.failed);
+        assert!(!report.satisfied);
+
+        // witnesses of rotations come from the key state
+        let rot = saidify(
+            json!({
+                "v": "", "t": "rot", "d": "", "i": serder.pre().unwrap(), "s": "1",
+                "p": serder.said().unwrap(), "kt": "0", "k": [], "nt": "0", "n": [],
+                "bt": "2", "br": [], "ba": [], "a": []
+            }),
+            &["d"],
+        );
+        let err = verify_witness_receipts(&rot, &sigs).unwrap_err();
+        assert!(err.to_string().starts_with("Witnesses of rot events"));
+    }
+}
@@ -0,0 +1,32 @@
+/// Outcome of verifying indexed signatures against a key list and its signing threshold
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VerificationReport {
+    /// Indices of the keys whose signatures verified, in the order the signatures were attached
+    pub verified: Vec<u32>,
+    /// Indices of signatures failing to verify, or referring past the end of the key list
+    pub failed: Vec<u32>,
+    /// Whether the verified indices satisfy the threshold
+    pub satisfied: bool,
+}
+
+/// Outcome of verifying non-transferable receipt couples
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReceiptReport {
+    /// Prefixes of the receiptors whose signatures verified
+    pub verified: Vec<String>,
+    /// Prefixes of the receiptors whose signatures failed to verify
+    pub failed: Vec<String>,
+}
+
+/// Outcome of verifying the signatures of a transferable signer against the key state
+/// established by the event the signatures refer to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransSignerReport {
+    pub prefix: String,
+    /// Sequence number of the establishment event
+    pub sn: u128,
+    /// Digest of the establishment event
+    pub said: String,
+    /// `None` if the key state established by the event is unknown
+    pub report: Option<VerificationReport>,
+}
@@ -0,0 +1,181 @@
+use cesride::{Matter, Siger};
+
+use crate::error::ParsideResult;
+use crate::message::{Group, TransIdxSigGroups, TransReceiptQuadruples};
+use crate::verify::controller_sigs::{satisfy, verify_sigers};
+use crate::verify::key_state::KeyStateProvider;
+use crate::verify::report::{TransSignerReport, VerificationReport};
+
+/// Prefix, sequence number and digest of the establishment event signatures refer to
+type Establishment = (String, u128, String);
+
+/// Verify transferable signature groups over the raw `body`, each against the key state
+/// established by the event its prefix, sequence number and digest refer to
+pub fn verify_trans_idx_sig_groups<P: KeyStateProvider + ?Sized>(
+    body: &[u8],
+    groups: &TransIdxSigGroups,
+    provider: &P,
+) -> ParsideResult<Vec<TransSignerReport>> {
+    groups
+        .value()
+        .iter()
+        .map(|group| {
+            let sigers = group.isigers.value().iter().map(|sig| &sig.siger);
+            let signer = (group.prefixer.qb64()?, group.seqner.sn()?, group.saider.qb64()?);
+            verify_signer(body, signer, sigers, provider)
+        })
+        .collect()
+}
+
+/// Verify transferable receipt quadruples over the raw receipted `body`. Quadruples of the same
+/// signer and establishment event are verified together against its signing threshold.
+pub fn verify_trans_receipts<P: KeyStateProvider + ?Sized>(
+    body: &[u8],
+    quadruples: &TransReceiptQuadruples,
+    provider: &P,
+) -> ParsideResult<Vec<TransSignerReport>> {
+    let mut signers: Vec<(Establishment, Vec<&Siger>)> = vec![];
+    for quadruple in quadruples.value() {
+        let signer = (quadruple.prefixer.qb64()?, quadruple.seqner.sn()?, quadruple.saider.qb64()?);
+        match signers.iter_mut().find(|(known, _)| *known == signer) {
+            Some((_, sigers)) => sigers.push(&quadruple.siger),
+            None => signers.push((signer, vec![&quadruple.siger])),
+        }
+    }
+
+    signers
+        .into_iter()
+        .map(|(signer, sigers)| verify_signer(body, signer, sigers, provider))
+        .collect()
+}
+
+fn verify_signer<'a, P: KeyStateProvider + ?Sized>(
+    body: &[u8],
+    (prefix, sn, said): Establishment,
+    sigers: impl IntoIterator<Item = &'a Siger>,
+    provider: &P,
+) -> ParsideResult<TransSignerReport> {
+    let report = match provider.establishment(&prefix, sn, &said) {
+        Some(state) => {
+            let (verified, failed) = verify_sigers(body, sigers, &state.verfers)?;
+            let satisfied = satisfy(&state.tholder, &state.verfers, &verified)?;
+            Some(VerificationReport { verified, failed, satisfied })
+        }
+        None => None,
+    };
+    Ok(TransSignerReport { prefix, sn, said, report })
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::error::ParsideError;
+    use crate::message::{
+        ControllerIdxSig, ControllerIdxSigs, TransIdxSigGroup, TransReceiptQuadruple,
+    };
+    use crate::utils::fixtures::signers;
+    use crate::verify::key_state::{InMemoryKeyStates, KeyState};
+    use cesride::data::dat;
+    use cesride::{Prefixer, Saider, Seqner, Signer, Tholder};
+
+    const BODY: &[u8] = b"signed body";
+    const PREFIX: &str = "EFhg5my9DuMU6gw1CVk6QgkmZKBttWSXDzVzWVmxh0_K";
+    const SAID: &str = "EADZ055vgh5utgSY3OOL1lW0m1pJ1W0Ia6-SVuGa0OqE";
+
+    fn key_states(signers: &[Signer]) -> InMemoryKeyStates {
+        let mut states = InMemoryKeyStates::new();
+        states.insert(KeyState {
+            prefix: PREFIX.to_string(),
+            sn: 1,
+            said: SAID.to_string(),
+            verfers: signers.iter().map(Signer::verfer).collect(),
+            tholder: Tholder::new_with_sith(&dat!("2")).unwrap(),
+        });
+        states
+    }
+
+    fn establishment(sn: u128) -> (Prefixer, Seqner, Saider) {
+        (
+            Prefixer::new_with_qb64(PREFIX).unwrap(),
+            Seqner::new_with_sn(sn).unwrap(),
+            Saider::new_with_qb64(SAID).unwrap(),
+        )
+    }
+
+    #[test]
+    pub fn test_verify_trans_idx_sig_groups() {
+        let signers = signers(3, true);
+        let states = key_states(&signers);
+        let isigers = ControllerIdxSigs::new(
+            (0..2)
+                .map(|i| ControllerIdxSig {
+                    siger: signers[i].sign_indexed(BODY, false, i as u32, None).unwrap(),
+                })
+                .collect(),
+        );
+        let group = |sn| {
+            let (prefixer, seqner, saider) = establishment(sn);
+            TransIdxSigGroup::new(prefixer, seqner, saider, isigers.clone())
+        };
+        let groups = TransIdxSigGroups::new(vec![group(1), group(2)]);
+
+        let reports = verify_trans_idx_sig_groups(BODY, &groups, &states).unwrap();
+        assert_eq!(2, reports.len());
+        assert_eq!(
+            (PREFIX, 1, SAID),
+            (reports[0].prefix.as_str(), reports[0].sn, reports[0].said.as_str())
+        );
+        let report = reports[0].report.as_ref().unwrap();
+        assert_eq!(vec![0, 1], report.verified);
+        assert!(report.satisfied);
+        // no key state is established at the second sequence number
+        assert!(reports[1].report.is_none());
+    }
+
+    #[test]
+    pub fn test_verify_trans_receipts() {
+        let signers = signers(3, true);
+        let states: Box<dyn KeyStateProvider> = Box::new(key_states(&signers));
+        let quadruple = |i: usize, body: &[u8]| {
+            let (prefixer, seqner, saider) = establishment(1);
+            let siger = signers[i].sign_indexed(body, false, i as u32, None).unwrap();
+            TransReceiptQuadruple::new(prefixer, seqner, saider, siger)
+        };
+
+        let quadruples = TransReceiptQuadruples::new(vec![quadruple(0, BODY), quadruple(2, BODY)]);
+        let reports = verify_trans_receipts(BODY, &quadruples, states.as_ref()).unwrap();
+        assert_eq!(1, reports.len());
+        let report = reports[0].report.as_ref().unwrap();
+        assert_eq!(vec![0, 2], report.verified);
+        assert!(report.satisfied);
+
+        let quadruples =
+            TransReceiptQuadruples::new(vec![quadruple(0, BODY), quadruple(1, b"other")]);
+        let reports = verify_trans_receipts(BODY, &quadruples, states.as_ref()).unwrap();
+        let report = reports[0].report.as_ref().unwrap();
+        assert_eq!((vec![0], vec![1]), (report.verified.clone(), report.failed.clone()));
+        assert!(!report.satisfied);
+    }
+
+    #[test]
+    pub fn test_verify_trans_receipts_with_mismatched_weights() {
+        let signers = signers(3, true);
+        let mut states = InMemoryKeyStates::new();
+        states.insert(KeyState {
+            prefix: PREFIX.to_string(),
+            sn: 1,
+            said: SAID.to_string(),
+            verfers: signers.iter().map(Signer::verfer).collect(),
+            tholder: Tholder::new_with_sith(&dat!(["1/2", "1/2"])).unwrap(),
+        });
+
+        // the last key has no weight
+        let (prefixer, seqner, saider) = establishment(1);
+        let siger = signers[2].sign_indexed(BODY, false, 2, None).unwrap();
+        let quadruples = TransReceiptQuadruples::new(vec![TransReceiptQuadruple::new(
+            prefixer, seqner, saider, siger,
+        )]);
+        let err = verify_trans_receipts(BODY, &quadruples, &states).unwrap_err();
+        assert!(matches!(err, ParsideError::Unexpected(..)));
+    }
+}